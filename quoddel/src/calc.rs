@@ -2,101 +2,362 @@ use crate::QuoddelResult;
 use seq_io::fasta::Reader;
 use std::ops::Add;
 
+/// Number and total length of contigs >= `min_length`
+/// for the "num contigs (>= x bp)" / "total length (>= x bp)" rows.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LengthBucket {
+    pub min_length: usize,
+    pub num_contigs: usize,
+    pub total_length: usize,
+}
+
+impl LengthBucket {
+    pub fn new(min_length: usize) -> Self {
+        LengthBucket {
+            min_length,
+            num_contigs: 0,
+            total_length: 0,
+        }
+    }
+
+    pub fn add(&mut self, seqlen: usize) {
+        if seqlen >= self.min_length {
+            self.num_contigs += 1;
+            self.total_length += seqlen;
+        }
+    }
+}
+
+/// Contigs with `min_length <= length < max_length`, one bin of the length histogram.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LengthBin {
+    pub min_length: usize,
+    /// None for the open-ended last bin.
+    pub max_length: Option<usize>,
+    pub num_contigs: usize,
+    pub total_length: usize,
+}
+
+/// Contig length histogram with bins starting at `bin_starts` (sorted and unique,
+/// the last bin is open-ended), or log10 bins (0, 10, 100, ...) covering the largest contig.
+/// A bin starting at 0 is added if missing so that every contig is counted.
+pub fn calc_length_histogram(lengths: &[usize], bin_starts: Option<&[usize]>) -> Vec<LengthBin> {
+    let mut starts = vec![0];
+    let mut open_ended = true;
+    match bin_starts {
+        Some(bin_starts) => starts.extend(bin_starts.iter().filter(|&&s| s > 0)),
+        None => {
+            let largest = lengths.iter().copied().max().unwrap_or(0);
+            let mut start: usize = 10;
+            while start <= largest {
+                starts.push(start);
+                start = start.saturating_mul(10);
+            }
+            starts.push(start);
+            open_ended = false;
+        }
+    }
+    let mut histogram: Vec<LengthBin> = starts
+        .windows(2)
+        .map(|w| (w[0], Some(w[1])))
+        .chain(open_ended.then(|| (starts[starts.len() - 1], None)))
+        .map(|(min_length, max_length)| LengthBin {
+            min_length,
+            max_length,
+            num_contigs: 0,
+            total_length: 0,
+        })
+        .collect();
+    for &seqlen in lengths {
+        let i = histogram.partition_point(|bin| bin.min_length <= seqlen) - 1;
+        histogram[i].num_contigs += 1;
+        histogram[i].total_length += seqlen;
+    }
+    histogram
+}
+
+/// Summary of contig lengths.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct LengthStats {
+    pub smallest: usize,
+    pub largest: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation.
+    pub sd: f64,
+}
+
+pub fn calc_length_stats(lengths: &[usize]) -> LengthStats {
+    if lengths.is_empty() {
+        return LengthStats::default();
+    }
+    let mut sorted = lengths.to_owned();
+    sorted.sort_unstable();
+    let n = sorted.len() as f64;
+    let mean = sorted.iter().map(|&l| l as u128).sum::<u128>() as f64 / n;
+    let variance = sorted
+        .iter()
+        .map(|&l| (l as f64 - mean) * (l as f64 - mean))
+        .sum::<f64>()
+        / n;
+    LengthStats {
+        smallest: sorted[0],
+        largest: sorted[sorted.len() - 1],
+        mean,
+        median: median(&sorted),
+        sd: variance.sqrt(),
+    }
+}
+
+/// Nx and Lx for one percentage, e.g. N50/L50 for `percent == 50`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NxStat {
+    pub percent: usize,
+    pub n: usize,
+    pub l: usize,
+}
+
 #[derive(Debug)]
 pub struct NLStats {
-    pub n50: usize,
-    pub n90: usize,
-    pub l50: usize,
-    pub l90: usize,
-}
-pub fn calc_stats(lengths: &[usize]) -> NLStats {
-    let fifty: f32 = (lengths.iter().map(|&x| x as f32).sum::<f32>()) * 0.5;
-    let ninety: f32 = (lengths.iter().map(|&x| x as f32).sum::<f32>()) * 0.9;
+    pub nx: Vec<NxStat>,
+    /// NGx/LGx in the same order as `nx`, empty without a genome size.
+    /// None if the assembly is too small to reach x% of the genome size.
+    pub ngx: Vec<Option<NxStat>>,
+    /// Nx for x = 0..=100, only filled if requested.
+    pub nx_curve: Vec<NxStat>,
+    /// NGx for x = 0..=100, only filled if requested and with a genome size.
+    pub ngx_curve: Vec<Option<NxStat>>,
+}
+
+pub fn calc_stats(
+    lengths: &[usize],
+    percentages: &[usize],
+    est_genome_size: Option<usize>,
+    with_curve: bool,
+) -> NLStats {
+    let total: usize = lengths.iter().sum();
     let mut tmp = lengths.to_owned();
     tmp.sort_by(|a, b| b.cmp(a));
-    let mut n90sum: usize = 0;
-    let mut n50sum: usize = 0;
-    let mut current_50: usize = 0;
-    let mut current_90: usize = 0;
-    let mut count_50: usize = 0;
-    let mut count_90: usize = 0;
-    for decreasing in tmp {
-        if n90sum >= ninety as usize {
-            break;
-        }
-        if n50sum < fifty as usize {
-            //todo test on edge case
-            n50sum += decreasing;
-            current_50 = decreasing;
-            count_50 += 1;
+    let nx = percentages
+        .iter()
+        .map(|&p| {
+            calc_nx(&tmp, total, p).unwrap_or(NxStat {
+                percent: p,
+                n: 0,
+                l: 0,
+            })
+        })
+        .collect();
+    let ngx = match est_genome_size {
+        Some(size) => percentages
+            .iter()
+            .map(|&p| calc_nx(&tmp, size, p))
+            .collect(),
+        None => Vec::new(),
+    };
+    let mut nx_curve = Vec::new();
+    let mut ngx_curve = Vec::new();
+    if with_curve {
+        nx_curve = calc_nx_curve(&tmp, total)
+            .iter()
+            .zip(0..)
+            .map(|(&stat, p)| {
+                stat.unwrap_or(NxStat {
+                    percent: p,
+                    n: 0,
+                    l: 0,
+                })
+            })
+            .collect();
+        if let Some(size) = est_genome_size {
+            ngx_curve = calc_nx_curve(&tmp, size);
         }
-        n90sum += decreasing;
-        current_90 = decreasing;
-        count_90 += 1;
     }
     NLStats {
-        n50: current_50,
-        n90: current_90,
-        l50: count_50,
-        l90: count_90,
+        nx,
+        ngx,
+        nx_curve,
+        ngx_curve,
     }
 }
 
-pub fn n90(lengths: &Vec<usize>) -> usize {
-    //todo de-uglify
-    let ninety: f32 = (lengths.iter().map(|&x| x as f32).sum::<f32>()) * 0.9;
-    let mut tmp = lengths.to_owned(); //clone();
-    tmp.sort_by(|a, b| b.cmp(a));
-    let mut n90sum: usize = 0;
-    let mut current: usize = 0;
-    for decreasing in tmp {
-        //println!("decreasing: {}", decreasing);
-        if n90sum >= ninety as usize {
-            break;
+/// Nx / Lx for lengths sorted in decreasing order, relative to `total`
+/// (assembly length for Nx, genome size for NGx).
+/// None if the lengths do not add up to `percent` of `total`.
+pub fn calc_nx(sorted_lengths: &[usize], total: usize, percent: usize) -> Option<NxStat> {
+    let mut sum: u64 = 0;
+    for (i, &decreasing) in sorted_lengths.iter().enumerate() {
+        sum += decreasing as u64;
+        if reaches_percent(sum, total, percent) {
+            return Some(NxStat {
+                percent,
+                n: decreasing,
+                l: i + 1,
+            });
         }
-        n90sum += decreasing;
-        current = decreasing;
     }
-    current
+    None
 }
 
-pub fn n50(lengths: &Vec<usize>) -> usize {
-    //todo oof ugly ey
-    //longest to shortest
-    let half: f32 = (lengths.iter().map(|&x| x as f32).sum::<f32>()) * 0.5;
-    let mut tmp = lengths.to_owned(); //.clone();
-    tmp.sort_by(|a, b| b.cmp(a));
-    let mut n50sum: usize = 0;
-    let mut current: usize = 0;
-    for decreasing in tmp {
-        if n50sum >= half as usize {
-            //todo...
-            break;
+/// Nx / Lx for x = 0..=100 in a single pass over lengths sorted in decreasing order.
+/// Same results as calling `calc_nx` for every x.
+pub fn calc_nx_curve(sorted_lengths: &[usize], total: usize) -> Vec<Option<NxStat>> {
+    let mut curve = Vec::with_capacity(101);
+    let mut sum: u64 = 0;
+    let mut count: usize = 0;
+    for percent in 0..=100 {
+        while (count == 0 || !reaches_percent(sum, total, percent)) && count < sorted_lengths.len()
+        {
+            sum += sorted_lengths[count] as u64;
+            count += 1;
+        }
+        if count > 0 && reaches_percent(sum, total, percent) {
+            curve.push(Some(NxStat {
+                percent,
+                n: sorted_lengths[count - 1],
+                l: count,
+            }));
+        } else {
+            curve.push(None);
+        }
+    }
+    curve
+}
+
+/// Whether `sum` is at least `percent` % of `total`, compared exactly
+/// as `100 * sum >= percent * total` instead of with a rounded threshold.
+fn reaches_percent(sum: u64, total: usize, percent: usize) -> bool {
+    100 * sum as u128 >= percent as u128 * total as u128
+}
+
+/// Area under the Nx curve: sum of squared lengths divided by `total`.
+/// Use the assembly length for auN and the estimated genome size for auNG.
+pub fn calc_aun(lengths: &[usize], total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let squares: u128 = lengths.iter().map(|&x| x as u128 * x as u128).sum();
+    squares as f64 / total as f64
+}
+
+/// Start and end (exclusive) of all runs of at least `min_length` N/n.
+pub fn find_n_runs(seq: &[u8], min_length: usize) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, &c) in seq.iter().enumerate() {
+        match (c == b'N' || c == b'n', start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                if i - s >= min_length {
+                    runs.push((s, i));
+                }
+                start = None;
+            }
+            _ => {}
         }
-        n50sum += decreasing;
-        current = decreasing;
     }
-    current
+    if let Some(s) = start {
+        if seq.len() - s >= min_length {
+            runs.push((s, seq.len()));
+        }
+    }
+    runs
+}
+
+/// Gap (N run) metrics of one assembly.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GapStats {
+    pub num_gaps: usize,
+    pub total_gap_length: usize,
+    pub largest_gap: usize,
+    pub mean_gap_length: f64,
+    pub median_gap_length: f64,
+    /// Gaps of exactly 100 Ns, NCBI's convention for gaps of unknown size.
+    pub num_gaps_100n: usize,
 }
 
-pub fn get_gc_num(seq: &Vec<u8>) -> usize {
+pub fn calc_gap_stats(gap_lengths: &[usize]) -> GapStats {
+    if gap_lengths.is_empty() {
+        return GapStats::default();
+    }
+    let mut sorted = gap_lengths.to_owned();
+    sorted.sort_unstable();
+    let total: usize = sorted.iter().sum();
+    GapStats {
+        num_gaps: sorted.len(),
+        total_gap_length: total,
+        largest_gap: sorted[sorted.len() - 1],
+        mean_gap_length: total as f64 / sorted.len() as f64,
+        median_gap_length: median(&sorted),
+        num_gaps_100n: sorted.iter().filter(|&&l| l == 100).count(),
+    }
+}
+
+/// Median of values sorted in increasing order, 0 if empty.
+pub fn median(sorted: &[usize]) -> f64 {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (sorted[mid - 1] + sorted[mid]) as f64 / 2.0,
+        _ => sorted[mid] as f64,
+    }
+}
+
+/// Contig coordinates (start, end exclusive) between the gaps of a scaffold of length `seqlen`.
+/// Empty pieces, e.g. from leading or trailing gaps, are left out.
+pub fn split_at_gaps(seqlen: usize, gaps: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut contigs = Vec::new();
+    let mut start: usize = 0;
+    for &(gap_start, gap_end) in gaps {
+        if gap_start > start {
+            contigs.push((start, gap_start));
+        }
+        start = gap_end;
+    }
+    if seqlen > start {
+        contigs.push((start, seqlen));
+    }
+    contigs
+}
+
+pub fn get_gc_num(seq: &[u8]) -> usize {
     seq.iter()
         .filter(|&&c| c == b'C' || c == b'G' || c == b'g' || c == b'c')
         .count()
 }
-pub fn get_at_num(seq: &Vec<u8>) -> usize {
+pub fn get_at_num(seq: &[u8]) -> usize {
     seq.iter()
         .filter(|&&c| c == b'A' || c == b'T' || c == b'a' || c == b't')
         .count()
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// Counts of all IUPAC nucleotide codes, case-insensitive,
+/// and of lowercase (soft-masked) characters.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct NucCount {
     pub(crate) num_a: usize,
     pub(crate) num_t: usize,
     pub(crate) num_c: usize,
     pub(crate) num_g: usize,
     pub(crate) num_n: usize,
+    // two- and three-base ambiguity codes
+    pub(crate) num_r: usize,
+    pub(crate) num_y: usize,
+    pub(crate) num_s: usize,
+    pub(crate) num_w: usize,
+    pub(crate) num_k: usize,
+    pub(crate) num_m: usize,
+    pub(crate) num_b: usize,
+    pub(crate) num_d: usize,
+    pub(crate) num_h: usize,
+    pub(crate) num_v: usize,
+    pub(crate) num_x: usize,
+    /// '-' and '.'
+    pub(crate) num_gap: usize,
+    pub(crate) num_other: usize,
+    pub(crate) num_lowercase: usize,
+    /// Number of lowercase stretches, only counted by `get_atgcn_num`.
+    pub(crate) num_lowercase_runs: usize,
 }
 impl Add for NucCount {
     type Output = Self;
@@ -108,45 +369,419 @@ impl Add for NucCount {
             num_g: self.num_g + other.num_g,
             num_c: self.num_c + other.num_c,
             num_n: self.num_n + other.num_n,
+            num_r: self.num_r + other.num_r,
+            num_y: self.num_y + other.num_y,
+            num_s: self.num_s + other.num_s,
+            num_w: self.num_w + other.num_w,
+            num_k: self.num_k + other.num_k,
+            num_m: self.num_m + other.num_m,
+            num_b: self.num_b + other.num_b,
+            num_d: self.num_d + other.num_d,
+            num_h: self.num_h + other.num_h,
+            num_v: self.num_v + other.num_v,
+            num_x: self.num_x + other.num_x,
+            num_gap: self.num_gap + other.num_gap,
+            num_other: self.num_other + other.num_other,
+            num_lowercase: self.num_lowercase + other.num_lowercase,
+            num_lowercase_runs: self.num_lowercase_runs + other.num_lowercase_runs,
         }
     }
 }
 
+/// How ambiguity codes are treated in the GC content.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct GcOptions {
+    /// Count S (G or C) as GC and W (A or T) as AT.
+    pub count_s: bool,
+    /// Add the remaining ambiguity codes to the denominator.
+    pub ambiguous_in_denominator: bool,
+}
+
+impl NucCount {
+    pub fn add_nuc(&mut self, nuc: u8) {
+        if nuc.is_ascii_lowercase() {
+            self.num_lowercase += 1;
+        }
+        match nuc {
+            b'A' | b'a' => self.num_a += 1,
+            b'T' | b't' => self.num_t += 1,
+            b'G' | b'g' => self.num_g += 1,
+            b'C' | b'c' => self.num_c += 1,
+            b'N' | b'n' => self.num_n += 1,
+            b'R' | b'r' => self.num_r += 1,
+            b'Y' | b'y' => self.num_y += 1,
+            b'S' | b's' => self.num_s += 1,
+            b'W' | b'w' => self.num_w += 1,
+            b'K' | b'k' => self.num_k += 1,
+            b'M' | b'm' => self.num_m += 1,
+            b'B' | b'b' => self.num_b += 1,
+            b'D' | b'd' => self.num_d += 1,
+            b'H' | b'h' => self.num_h += 1,
+            b'V' | b'v' => self.num_v += 1,
+            b'X' | b'x' => self.num_x += 1,
+            b'-' | b'.' => self.num_gap += 1,
+            _ => self.num_other += 1,
+        }
+    }
+
+    /// R, Y, S, W, K, M, B, D, H and V.
+    pub fn num_ambiguous(&self) -> usize {
+        self.num_r
+            + self.num_y
+            + self.num_s
+            + self.num_w
+            + self.num_k
+            + self.num_m
+            + self.num_b
+            + self.num_d
+            + self.num_h
+            + self.num_v
+    }
+
+    /// Characters that are neither a base, N nor an ambiguity code.
+    pub fn num_non_iupac(&self) -> usize {
+        self.num_x + self.num_gap + self.num_other
+    }
+
+    /// Numerator and denominator of the GC content; by default only A, C, G and T are counted.
+    pub fn gc_counts(&self, options: GcOptions) -> (usize, usize) {
+        let mut gc = self.num_g + self.num_c;
+        let mut total = gc + self.num_a + self.num_t;
+        if options.count_s {
+            gc += self.num_s;
+            total += self.num_s + self.num_w;
+        }
+        if options.ambiguous_in_denominator {
+            total += self.num_ambiguous();
+            if options.count_s {
+                total -= self.num_s + self.num_w;
+            }
+        }
+        (gc, total)
+    }
+
+    /// GC fraction; by default only A, C, G and T are counted.
+    pub fn gc_fraction(&self, options: GcOptions) -> f64 {
+        let (gc, total) = self.gc_counts(options);
+        gc as f64 / total as f64
+    }
+}
+
 pub fn count_nuc(nuc: &u8) -> NucCount {
-    let mut nuc_count = NucCount {
-        num_a: 0,
-        num_t: 0,
-        num_c: 0,
-        num_g: 0,
-        num_n: 0,
+    let mut nuc_count = NucCount::default();
+    nuc_count.add_nuc(*nuc);
+    nuc_count
+}
+pub fn get_atgcn_num(seq: &[u8]) -> NucCount {
+    get_atgcn_homopolymer_num(seq, usize::MAX).0
+}
+
+/// Homopolymer runs of A, C, G and T (in this order) of at least `min_length`, case-insensitive.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct HomopolymerStats {
+    pub num_runs: [usize; 4],
+    pub longest: [usize; 4],
+    /// Bases inside runs of at least `min_length`.
+    pub num_bases_in_runs: usize,
+}
+impl Add for HomopolymerStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut sum = self;
+        for i in 0..4 {
+            sum.num_runs[i] += other.num_runs[i];
+            sum.longest[i] = sum.longest[i].max(other.longest[i]);
+        }
+        sum.num_bases_in_runs += other.num_bases_in_runs;
+        sum
+    }
+}
+
+impl HomopolymerStats {
+    fn end_run(&mut self, base: usize, length: usize, min_length: usize) {
+        self.longest[base] = self.longest[base].max(length);
+        if length >= min_length {
+            self.num_runs[base] += 1;
+            self.num_bases_in_runs += length;
+        }
+    }
+}
+
+/// Nucleotide counts and homopolymer runs of at least `min_homopolymer` in one pass.
+pub fn get_atgcn_homopolymer_num(
+    seq: &[u8],
+    min_homopolymer: usize,
+) -> (NucCount, HomopolymerStats) {
+    let mut nuc_count = NucCount::default();
+    let mut homopolymers = HomopolymerStats::default();
+    let mut prev_lowercase = false;
+    // current run: (base index, length)
+    let mut run: Option<(usize, usize)> = None;
+    for &nuc in seq {
+        nuc_count.add_nuc(nuc);
+        let lowercase = nuc.is_ascii_lowercase();
+        if lowercase && !prev_lowercase {
+            nuc_count.num_lowercase_runs += 1;
+        }
+        prev_lowercase = lowercase;
+
+        let base = base_index(nuc);
+        run = match (run, base) {
+            (Some((prev, length)), Some(b)) if prev == b => Some((b, length + 1)),
+            (prev_run, b) => {
+                if let Some((prev, length)) = prev_run {
+                    homopolymers.end_run(prev, length, min_homopolymer);
+                }
+                b.map(|b| (b, 1))
+            }
+        };
+    }
+    if let Some((prev, length)) = run {
+        homopolymers.end_run(prev, length, min_homopolymer);
+    }
+    (nuc_count, homopolymers)
+}
+
+/// Largest k for the k-mer entropy, 4^k counts are kept per assembly.
+pub const MAX_ENTROPY_K: usize = 8;
+
+/// Shannon entropy in bits of the distribution given by `counts`, 0 if all counts are 0.
+pub fn shannon_entropy<I>(counts: I) -> f64
+where
+    I: IntoIterator<Item = usize>,
+    I::IntoIter: Clone,
+{
+    let counts = counts.into_iter();
+    let total: usize = counts.clone().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    counts
+        .filter(|&c| c > 0)
+        .map(|c| {
+            let p = c as f64 / total as f64;
+            p * (1.0 / p).log2()
+        })
+        .sum::<f64>()
+}
+
+/// Shannon entropy in bits of the k-mers (k = 1: bases) of each sequence, at most 2k.
+/// k-mers containing N or ambiguity codes are skipped.
+/// The 4^k counts are shared by all sequences and only the k-mers seen are reset,
+/// so short contigs don't pay for large k.
+pub struct EntropyCounter {
+    k: usize,
+    counts: Vec<usize>,
+    /// Distinct k-mers of the current sequence.
+    seen: Vec<usize>,
+}
+
+impl EntropyCounter {
+    pub fn new(k: usize) -> Self {
+        assert!(
+            (1..=MAX_ENTROPY_K).contains(&k),
+            "k must be between 1 and {}",
+            MAX_ENTROPY_K
+        );
+        EntropyCounter {
+            k,
+            counts: vec![0; 1 << (2 * k)],
+            seen: Vec::new(),
+        }
+    }
+
+    pub fn entropy(&mut self, seq: &[u8]) -> f64 {
+        let mask = (1 << (2 * self.k)) - 1;
+        let mut kmer: usize = 0;
+        let mut valid: usize = 0;
+        for &nuc in seq {
+            match base_index(nuc) {
+                Some(b) => {
+                    kmer = ((kmer << 2) | b) & mask;
+                    valid += 1;
+                    if valid >= self.k {
+                        if self.counts[kmer] == 0 {
+                            self.seen.push(kmer);
+                        }
+                        self.counts[kmer] += 1;
+                    }
+                }
+                None => valid = 0,
+            }
+        }
+        let counts = &self.counts;
+        let entropy = shannon_entropy(self.seen.iter().map(|&kmer| counts[kmer]));
+        for &kmer in &self.seen {
+            self.counts[kmer] = 0;
+        }
+        self.seen.clear();
+        entropy
+    }
+}
+
+/// Per-contig entropy summary of one assembly.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct EntropyStats {
+    pub k: usize,
+    /// Contigs below this entropy are counted as low-entropy.
+    pub min_entropy: f64,
+    pub mean: f64,
+    pub num_low_entropy: usize,
+}
+
+pub fn calc_entropy_stats(entropies: &[f64], k: usize, min_entropy: f64) -> EntropyStats {
+    let mean = if entropies.is_empty() {
+        0.0
+    } else {
+        entropies.iter().sum::<f64>() / entropies.len() as f64
     };
+    EntropyStats {
+        k,
+        min_entropy,
+        mean,
+        num_low_entropy: entropies.iter().filter(|&&e| e < min_entropy).count(),
+    }
+}
+
+/// 2-bit index of an unambiguous base, case-insensitive.
+pub fn base_index(nuc: u8) -> Option<usize> {
     match nuc {
-        b'A' | b'a' => nuc_count.num_a += 1,
-        b'T' | b't' => nuc_count.num_t += 1,
-        b'G' | b'g' => nuc_count.num_g += 1,
-        b'C' | b'c' => nuc_count.num_c += 1,
-        b'N' | b'n' => nuc_count.num_n += 1,
-        _ => {}
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
     }
-    nuc_count
 }
-pub fn get_atgcn_num(seq: &Vec<u8>) -> NucCount {
-    seq.iter()
-        //.filter(|&&c| c in &[b'a',b'A',b't',b'T', b'g',b'G',b'c',b'C', b'N', b'n'])
-        .map(count_nuc)
-        .fold(
-            NucCount {
-                num_a: 0,
-                num_t: 0,
-                num_c: 0,
-                num_g: 0,
-                num_n: 0,
-            },
-            |count, new| count + new,
-        )
+
+/// Complement of a base or IUPAC ambiguity code, uppercase; S, W, N and
+/// other characters are kept.
+pub fn complement(nuc: u8) -> u8 {
+    match nuc.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        other => other,
+    }
 }
 
-pub fn calc_gc(file: &str, length_cutoff: usize) -> QuoddelResult<f32> {
+/// Reverse complement, uppercase, see `complement`.
+pub fn reverse_complement(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|&c| complement(c)).collect()
+}
+
+pub const DINUCLEOTIDES: [&str; 16] = [
+    "AA", "AC", "AG", "AT", "CA", "CC", "CG", "CT", "GA", "GC", "GG", "GT", "TA", "TC", "TG", "TT",
+];
+
+/// Counts of the 16 dinucleotides, in the order of `DINUCLEOTIDES`.
+/// Pairs involving N or ambiguity codes are not counted.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct DinucCount {
+    pub counts: [usize; 16],
+}
+
+impl Add for DinucCount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut counts = self.counts;
+        for (count, o) in counts.iter_mut().zip(other.counts) {
+            *count += o;
+        }
+        Self { counts }
+    }
+}
+
+impl DinucCount {
+    pub fn num_cpg(&self) -> usize {
+        self.counts[6]
+    }
+
+    /// CpG observed/expected: CpG * L / (C * G), with L the number of A, C, G and T.
+    /// NaN without C or G.
+    pub fn cpg_o_e(&self, nuc_count: &NucCount) -> f64 {
+        let acgt = nuc_count.num_a + nuc_count.num_c + nuc_count.num_g + nuc_count.num_t;
+        let expected = nuc_count.num_c as f64 * nuc_count.num_g as f64 / acgt as f64;
+        self.num_cpg() as f64 / expected
+    }
+}
+
+pub fn get_dinuc_num(seq: &[u8]) -> DinucCount {
+    let mut dinuc_count = DinucCount::default();
+    for pair in seq.windows(2) {
+        if let (Some(first), Some(second)) = (base_index(pair[0]), base_index(pair[1])) {
+            dinuc_count.counts[first * 4 + second] += 1;
+        }
+    }
+    dinuc_count
+}
+
+/// One 1% bin of the per-contig GC histogram.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct GcBin {
+    pub num_contigs: usize,
+    pub total_length: usize,
+}
+
+/// Distribution of per-contig GC (%), unweighted.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GcDistribution {
+    pub mean: f64,
+    pub sd: f64,
+    pub min: f64,
+    pub max: f64,
+    /// 100 bins of 1%, 100% GC goes into the last bin.
+    pub histogram: Vec<GcBin>,
+}
+
+/// GC (%) distribution from (GC bases, counted bases, length) per contig,
+/// see `NucCount::gc_counts`. Contigs without any counted bases are left out.
+pub fn calc_gc_distribution(contigs: &[(usize, usize, usize)]) -> GcDistribution {
+    let mut histogram = vec![GcBin::default(); 100];
+    let percents: Vec<f64> = contigs
+        .iter()
+        .filter(|(_, total, _)| *total > 0)
+        .map(|&(gc, total, seqlen)| {
+            let bin = (gc * 100 / total).min(99);
+            histogram[bin].num_contigs += 1;
+            histogram[bin].total_length += seqlen;
+            gc as f64 * 100.0 / total as f64
+        })
+        .collect();
+    if percents.is_empty() {
+        return GcDistribution {
+            histogram,
+            ..Default::default()
+        };
+    }
+    let n = percents.len() as f64;
+    let mean = percents.iter().sum::<f64>() / n;
+    let variance = percents
+        .iter()
+        .map(|p| (p - mean) * (p - mean))
+        .sum::<f64>()
+        / n;
+    GcDistribution {
+        mean,
+        sd: variance.sqrt(),
+        min: percents.iter().cloned().fold(f64::INFINITY, f64::min),
+        max: percents.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        histogram,
+    }
+}
+
+pub fn calc_gc(file: &str, length_cutoff: usize) -> QuoddelResult<f64> {
     let mut reader_gc = Reader::from_path(file)?;
     //let mut reader_at = Reader::from_path(file)?;
 
@@ -155,19 +790,33 @@ pub fn calc_gc(file: &str, length_cutoff: usize) -> QuoddelResult<f32> {
         .map(|x| x.unwrap().seq)
         .filter(|x| x.len() >= length_cutoff)
         .collect();
-    let gcnum = filtered.iter().map(get_gc_num).sum::<usize>();
-    let atnum = filtered.iter().map(get_at_num).sum::<usize>();
+    let gcnum = filtered.iter().map(|s| get_gc_num(s)).sum::<usize>();
+    let atnum = filtered.iter().map(|s| get_at_num(s)).sum::<usize>();
 
-    Ok(gcnum as f32 / (atnum + gcnum) as f32)
+    Ok(gcnum as f64 / (atnum + gcnum) as f64)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        calc::{n50, n90},
-        get_at_num, get_atgcn_num, get_gc_num, NucCount,
+        calc::{
+            calc_aun, calc_gap_stats, calc_length_histogram, calc_length_stats, calc_nx,
+            calc_stats, find_n_runs, median, split_at_gaps, GapStats, LengthBin, LengthStats,
+            NxStat,
+        },
+        calc_entropy_stats, calc_gc_distribution, get_at_num, get_atgcn_homopolymer_num,
+        get_atgcn_num, get_dinuc_num, get_gc_num, reverse_complement, shannon_entropy,
+        EntropyCounter, GcBin, GcOptions, NucCount,
     };
 
+    fn n50(lengths: &[usize]) -> usize {
+        calc_stats(lengths, &[50], None, false).nx[0].n
+    }
+
+    fn n90(lengths: &[usize]) -> usize {
+        calc_stats(lengths, &[90], None, false).nx[0].n
+    }
+
     #[test]
     fn test_n50_odd() {
         let len_vec: Vec<usize> = vec![15, 11, 12, 16, 14, 10, 1];
@@ -239,23 +888,274 @@ mod tests {
         let result = n90(&len_vec);
         assert_eq!(result, expected);
     }
+    #[test]
+    fn test_ng50_ng90() {
+        let len_vec: Vec<usize> = vec![15, 11, 12, 16, 14, 10, 1];
+        // 79 bp assembly, genome 100 bp
+        // 16+15+14 = 45 < 50, 16+15+14+12 = 57 >= 50
+        let result = calc_stats(&len_vec, &[50, 90], Some(100), false);
+        assert_eq!(
+            result.ngx[0],
+            Some(NxStat {
+                percent: 50,
+                n: 12,
+                l: 4
+            })
+        );
+        // 79 < 90
+        assert_eq!(result.ngx[1], None);
+    }
+
+    #[test]
+    fn test_ng_without_genome_size() {
+        let len_vec: Vec<usize> = vec![15, 11, 12, 16, 14, 10, 1];
+        let result = calc_stats(&len_vec, &[50], None, false);
+        assert_eq!(result.nx[0].n, 14);
+        assert!(result.ngx.is_empty());
+    }
+
+    #[test]
+    fn test_nx_arbitrary_percentages() {
+        let len_vec: Vec<usize> = vec![15, 11, 12, 16, 14, 10, 1];
+        // 79 bp: 16, 31, 45, 57, 68, 78, 79
+        let result = calc_stats(&len_vec, &[0, 10, 25, 75, 95, 100], None, false);
+        let n_l: Vec<(usize, usize, usize)> =
+            result.nx.iter().map(|s| (s.percent, s.n, s.l)).collect();
+        assert_eq!(
+            n_l,
+            vec![
+                (0, 16, 1),
+                (10, 16, 1),
+                (25, 15, 2),
+                (75, 11, 5),
+                (95, 10, 6),
+                (100, 1, 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nx_curve_matches_nx() {
+        let len_vec: Vec<usize> = vec![15, 11, 12, 16, 14, 10, 1];
+        let result = calc_stats(&len_vec, &[], Some(70), true);
+        assert_eq!(result.nx_curve.len(), 101);
+        assert_eq!(result.ngx_curve.len(), 101);
+        let mut sorted = len_vec.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+        for percent in 0..=100 {
+            let expected = calc_nx(&sorted, 79, percent).unwrap();
+            assert_eq!(result.nx_curve[percent], expected);
+            assert_eq!(result.ngx_curve[percent], calc_nx(&sorted, 70, percent));
+        }
+    }
+
+    #[test]
+    fn test_nx_exactly_half() {
+        // cumulative sum hits 50% exactly with the second contig
+        let len_vec: Vec<usize> = vec![5, 5, 5, 5];
+        let result = calc_stats(&len_vec, &[50], None, true);
+        assert_eq!(
+            result.nx[0],
+            NxStat {
+                percent: 50,
+                n: 5,
+                l: 2
+            }
+        );
+        assert_eq!(result.nx_curve[50], result.nx[0]);
+        // 50% of 9 is 4.5, so 4 bp are not enough
+        let len_vec: Vec<usize> = vec![4, 3, 2];
+        let result = calc_stats(&len_vec, &[50], Some(8), true);
+        assert_eq!(
+            result.nx[0],
+            NxStat {
+                percent: 50,
+                n: 3,
+                l: 2
+            }
+        );
+        assert_eq!(
+            result.ngx[0],
+            Some(NxStat {
+                percent: 50,
+                n: 4,
+                l: 1
+            })
+        );
+        assert_eq!(result.nx_curve[50], result.nx[0]);
+        assert_eq!(result.ngx_curve[50], result.ngx[0]);
+    }
+
+    #[test]
+    fn test_nx_large_assembly() {
+        // 40 Gbp: half of the total is 20_000_000_000.5, not reached by the first contig
+        let len_vec: Vec<usize> = vec![20_000_000_000, 20_000_000_000, 1];
+        let result = calc_stats(&len_vec, &[50, 100], None, true);
+        assert_eq!(
+            result.nx,
+            vec![
+                NxStat {
+                    percent: 50,
+                    n: 20_000_000_000,
+                    l: 2
+                },
+                NxStat {
+                    percent: 100,
+                    n: 1,
+                    l: 3
+                },
+            ]
+        );
+        assert_eq!(result.nx_curve[50], result.nx[0]);
+        assert_eq!(result.nx_curve[100], result.nx[1]);
+    }
+
+    #[test]
+    fn test_nx_curve_too_small_for_genome() {
+        let result = calc_stats(&[30, 10], &[], Some(100), true);
+        // 30 >= 30%, 40 >= 40%, but not 41%
+        assert_eq!(
+            result.ngx_curve[40],
+            Some(NxStat {
+                percent: 40,
+                n: 10,
+                l: 2
+            })
+        );
+        assert_eq!(result.ngx_curve[41], None);
+        assert_eq!(result.ngx_curve[100], None);
+    }
+
+    #[test]
+    fn test_aun() {
+        let len_vec: Vec<usize> = vec![150, 1, 2, 3];
+        // (22500 + 1 + 4 + 9) / 156
+        assert_eq!(calc_aun(&len_vec, 156), 22514.0 / 156.0);
+        // auNG with a 1000 bp genome
+        assert_eq!(calc_aun(&len_vec, 1000), 22.514);
+        assert_eq!(calc_aun(&[], 0), 0.0);
+    }
+
+    #[test]
+    fn test_length_histogram_log10() {
+        let histogram = calc_length_histogram(&[5, 10, 99, 100, 2500], None);
+        let bins: Vec<(usize, Option<usize>, usize, usize)> = histogram
+            .iter()
+            .map(|b| (b.min_length, b.max_length, b.num_contigs, b.total_length))
+            .collect();
+        assert_eq!(
+            bins,
+            vec![
+                (0, Some(10), 1, 5),
+                (10, Some(100), 2, 109),
+                (100, Some(1000), 1, 100),
+                (1000, Some(10000), 1, 2500),
+            ]
+        );
+        assert_eq!(
+            calc_length_histogram(&[], None),
+            vec![LengthBin {
+                min_length: 0,
+                max_length: Some(10),
+                num_contigs: 0,
+                total_length: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_length_histogram_user_bins() {
+        let histogram = calc_length_histogram(&[5, 10, 99, 100, 2500], Some(&[10, 1000]));
+        let bins: Vec<(usize, Option<usize>, usize, usize)> = histogram
+            .iter()
+            .map(|b| (b.min_length, b.max_length, b.num_contigs, b.total_length))
+            .collect();
+        assert_eq!(
+            bins,
+            vec![
+                (0, Some(10), 1, 5),
+                (10, Some(1000), 3, 209),
+                (1000, None, 1, 2500),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_length_stats() {
+        assert_eq!(
+            calc_length_stats(&[10, 2, 4, 8]),
+            LengthStats {
+                smallest: 2,
+                largest: 10,
+                mean: 6.0,
+                median: 6.0,
+                sd: 10.0_f64.sqrt(),
+            }
+        );
+        assert_eq!(calc_length_stats(&[7]).sd, 0.0);
+        assert_eq!(calc_length_stats(&[]), LengthStats::default());
+    }
+
+    #[test]
+    fn test_find_n_runs() {
+        let seq = b"NNacgtnnnNacgNNNN";
+        assert_eq!(find_n_runs(seq, 1), vec![(0, 2), (6, 10), (13, 17)]);
+        assert_eq!(find_n_runs(seq, 4), vec![(6, 10), (13, 17)]);
+        assert_eq!(find_n_runs(seq, 5), vec![]);
+        assert_eq!(find_n_runs(b"", 1), vec![]);
+    }
+
+    #[test]
+    fn test_split_at_gaps() {
+        let seq = b"NNacgtnnnNacgNNNN";
+        let gaps = find_n_runs(seq, 2);
+        assert_eq!(split_at_gaps(seq.len(), &gaps), vec![(2, 6), (10, 13)]);
+        assert_eq!(split_at_gaps(5, &[]), vec![(0, 5)]);
+        assert_eq!(split_at_gaps(4, &[(0, 4)]), vec![]);
+    }
+
+    #[test]
+    fn test_gap_stats() {
+        let result = calc_gap_stats(&[100, 1, 5, 100, 20]);
+        assert_eq!(
+            result,
+            GapStats {
+                num_gaps: 5,
+                total_gap_length: 226,
+                largest_gap: 100,
+                mean_gap_length: 45.2,
+                median_gap_length: 20.0,
+                num_gaps_100n: 2,
+            }
+        );
+        assert_eq!(calc_gap_stats(&[]), GapStats::default());
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&[]), 0.0);
+        assert_eq!(median(&[3]), 3.0);
+        assert_eq!(median(&[1, 2, 4, 10]), 3.0);
+        assert_eq!(median(&[1, 2, 4]), 2.0);
+    }
+
     #[test]
     fn test_get_gc_num() {
-        let seq: Vec<u8> = vec!['A', 'T', 'G', 'C'].iter().map(|&x| x as u8).collect();
+        let seq: Vec<u8> = ['A', 'T', 'G', 'C'].iter().map(|&x| x as u8).collect();
         let expected: usize = 2;
         let result = get_gc_num(&seq);
         assert_eq!(result, expected);
     }
     #[test]
     fn test_get_at_num() {
-        let seq: Vec<u8> = vec!['A', 'T', 'G', 'C'].iter().map(|&x| x as u8).collect();
+        let seq: Vec<u8> = ['A', 'T', 'G', 'C'].iter().map(|&x| x as u8).collect();
         let expected: usize = 2;
         let result = get_gc_num(&seq);
         assert_eq!(result, expected);
     }
     #[test]
     fn test_get_gc_num_n() {
-        let seq: Vec<u8> = vec!['A', 'N', 'T', 'G', 'C', 'a', 'N']
+        let seq: Vec<u8> = ['A', 'N', 'T', 'G', 'C', 'a', 'N']
             .iter()
             .map(|&x| x as u8)
             .collect();
@@ -265,7 +1165,7 @@ mod tests {
     }
     #[test]
     fn test_get_gc_num_lc() {
-        let seq: Vec<u8> = vec!['A', 'N', 'T', 'g', 'C', 'N', 'G']
+        let seq: Vec<u8> = ['A', 'N', 'T', 'g', 'C', 'N', 'G']
             .iter()
             .map(|&x| x as u8)
             .collect();
@@ -275,7 +1175,7 @@ mod tests {
     }
     #[test]
     fn test_get_at_num_lc() {
-        let seq: Vec<u8> = vec!['a', 'N', 't', 'g', 'C', 'N', 'G']
+        let seq: Vec<u8> = ['a', 'N', 't', 'g', 'C', 'N', 'G']
             .iter()
             .map(|&x| x as u8)
             .collect();
@@ -291,7 +1191,7 @@ mod tests {
             .collect::<Vec<Vec<u8>>>();
         let expected: [usize; 4] = [10, 1, 11, 0];
         for (seq, expected) in seqs.iter().zip(expected) {
-            assert_eq!(get_gc_num(&seq), expected);
+            assert_eq!(get_gc_num(seq), expected);
         }
     }
     #[test]
@@ -302,7 +1202,7 @@ mod tests {
             .collect::<Vec<Vec<u8>>>();
         let expected: [usize; 4] = [10, 1, 13, 0];
         for (seq, expected) in seqs.iter().zip(expected) {
-            assert_eq!(get_gc_num(&seq), expected);
+            assert_eq!(get_gc_num(seq), expected);
         }
     }
     #[test]
@@ -313,7 +1213,7 @@ mod tests {
             .collect::<Vec<Vec<u8>>>();
         let expected: [usize; 4] = [10, 1, 13, 0];
         for (seq, expected) in seqs.iter().zip(expected) {
-            assert_eq!(get_gc_num(&seq), expected);
+            assert_eq!(get_gc_num(seq), expected);
         }
     }
 
@@ -330,6 +1230,9 @@ mod tests {
                 num_c: 0,
                 num_g: 10,
                 num_n: 1,
+                num_lowercase: 14,
+                num_lowercase_runs: 2,
+                ..Default::default()
             },
             NucCount {
                 num_a: 1,
@@ -337,6 +1240,9 @@ mod tests {
                 num_c: 0,
                 num_g: 1,
                 num_n: 0,
+                num_lowercase: 3,
+                num_lowercase_runs: 1,
+                ..Default::default()
             },
             NucCount {
                 num_a: 2,
@@ -344,6 +1250,9 @@ mod tests {
                 num_c: 2,
                 num_g: 11,
                 num_n: 2,
+                num_lowercase: 16,
+                num_lowercase_runs: 3,
+                ..Default::default()
             },
             NucCount {
                 num_a: 0,
@@ -351,6 +1260,9 @@ mod tests {
                 num_c: 0,
                 num_g: 0,
                 num_n: 0,
+                num_lowercase: 2,
+                num_lowercase_runs: 1,
+                ..Default::default()
             },
         ];
         let mut res: Vec<NucCount> = Vec::new();
@@ -360,24 +1272,184 @@ mod tests {
             num_g: 22,
             num_c: 2,
             num_n: 3,
+            num_lowercase: 35,
+            num_lowercase_runs: 7,
+            ..Default::default()
         };
         for (s, ex) in seqs.iter().zip(expected) {
-            let count = get_atgcn_num(&s);
+            let count = get_atgcn_num(s);
             res.push(count);
             assert_eq!(count, ex)
         }
         assert_eq!(
-            res.iter().fold(
-                NucCount {
-                    num_a: 0,
-                    num_t: 0,
-                    num_c: 0,
-                    num_g: 0,
-                    num_n: 0,
-                },
-                |count, &new| count + new,
-            ),
+            res.iter()
+                .fold(NucCount::default(), |count, &new| count + new),
             expected_total
         )
     }
+
+    #[test]
+    fn test_nuc_count_iupac() {
+        let count = get_atgcn_num(b"ACGTNRYSWKMBDHVXU-.acgtnrysw*");
+        assert_eq!(count.num_a, 2);
+        assert_eq!(count.num_n, 2);
+        assert_eq!(count.num_s, 2);
+        assert_eq!(count.num_w, 2);
+        assert_eq!(count.num_x, 1);
+        assert_eq!(count.num_gap, 2);
+        // U and *
+        assert_eq!(count.num_other, 2);
+        assert_eq!(count.num_ambiguous(), 14);
+        assert_eq!(count.num_non_iupac(), 5);
+    }
+
+    #[test]
+    fn test_nuc_count_lowercase() {
+        let count = get_atgcn_num(b"acgtACGTnnNNacGT-a");
+        assert_eq!(count.num_lowercase, 9);
+        assert_eq!(count.num_lowercase_runs, 4);
+        assert_eq!(get_atgcn_num(b"ACGT").num_lowercase_runs, 0);
+        let total = get_atgcn_num(b"aaTT") + get_atgcn_num(b"ttAA");
+        assert_eq!(total.num_lowercase, 4);
+        assert_eq!(total.num_lowercase_runs, 2);
+    }
+
+    #[test]
+    fn test_gc_fraction_options() {
+        let count = get_atgcn_num(b"GGCCAATTSSWRN");
+        assert_eq!(count.gc_fraction(GcOptions::default()), 0.5);
+        let with_s = GcOptions {
+            count_s: true,
+            ambiguous_in_denominator: false,
+        };
+        // 6 / 11
+        assert_eq!(count.gc_fraction(with_s), 6.0 / 11.0);
+        let ambiguous = GcOptions {
+            count_s: false,
+            ambiguous_in_denominator: true,
+        };
+        // 4 / 12
+        assert_eq!(count.gc_fraction(ambiguous), 4.0 / 12.0);
+        let both = GcOptions {
+            count_s: true,
+            ambiguous_in_denominator: true,
+        };
+        assert_eq!(count.gc_fraction(both), 6.0 / 12.0);
+    }
+
+    #[test]
+    fn test_gc_distribution() {
+        let contigs = [
+            (50, 100, 100),
+            (1, 4, 10),
+            (5, 5, 5),
+            (0, 0, 7),
+            (51, 200, 20),
+        ];
+        let result = calc_gc_distribution(&contigs);
+        assert_eq!(result.min, 25.0);
+        assert_eq!(result.max, 100.0);
+        assert_eq!(result.mean, (50.0 + 25.0 + 100.0 + 25.5) / 4.0);
+        assert!((result.sd - 30.517).abs() < 0.001);
+        assert_eq!(result.histogram.len(), 100);
+        assert_eq!(
+            result.histogram[25],
+            GcBin {
+                num_contigs: 2,
+                total_length: 30
+            }
+        );
+        assert_eq!(result.histogram[50].total_length, 100);
+        assert_eq!(result.histogram[99].num_contigs, 1);
+        let empty = calc_gc_distribution(&[]);
+        assert_eq!(empty.mean, 0.0);
+        assert_eq!(empty.histogram.len(), 100);
+        // 0.29 * 100.0 and 0.57 * 100.0 are just below 29 and 57
+        let exact = calc_gc_distribution(&[(29, 100, 100), (57, 100, 100)]);
+        assert_eq!(exact.histogram[29].num_contigs, 1);
+        assert_eq!(exact.histogram[57].num_contigs, 1);
+    }
+
+    #[test]
+    fn test_dinuc_count() {
+        let count = get_dinuc_num(b"ACGcgNCGTa");
+        // AC CG Gc cg - - CG GT Ta
+        assert_eq!(count.counts[1], 1);
+        assert_eq!(count.num_cpg(), 3);
+        assert_eq!(count.counts[9], 1);
+        assert_eq!(count.counts[11], 1);
+        assert_eq!(count.counts[12], 1);
+        assert_eq!(count.counts.iter().sum::<usize>(), 7);
+        assert_eq!(get_dinuc_num(b"A").counts, [0; 16]);
+    }
+
+    #[test]
+    fn test_cpg_o_e() {
+        let seq = b"ACGTACGTNNCCGG";
+        let dinuc = get_dinuc_num(seq);
+        let nuc = get_atgcn_num(seq);
+        // 3 CpG * 12 / (4 C * 4 G)
+        assert_eq!(dinuc.cpg_o_e(&nuc), 2.25);
+        let no_gc = b"AATT";
+        assert!(get_dinuc_num(no_gc).cpg_o_e(&get_atgcn_num(no_gc)).is_nan());
+        let total = get_dinuc_num(b"CG") + get_dinuc_num(b"CG");
+        assert_eq!(total.num_cpg(), 2);
+    }
+
+    #[test]
+    fn test_homopolymers() {
+        let seq = b"AAAAcCCcNGGGGGtTaaaAANTTTTT";
+        let (nuc, homopolymers) = get_atgcn_homopolymer_num(seq, 4);
+        assert_eq!(nuc, get_atgcn_num(seq));
+        assert_eq!(homopolymers.num_runs, [2, 1, 1, 1]);
+        assert_eq!(homopolymers.longest, [5, 4, 5, 5]);
+        assert_eq!(homopolymers.num_bases_in_runs, 4 + 4 + 5 + 5 + 5);
+        let (_, none) = get_atgcn_homopolymer_num(seq, 6);
+        assert_eq!(none.num_runs, [0; 4]);
+        assert_eq!(none.longest, [5, 4, 5, 5]);
+        let sum = homopolymers + none;
+        assert_eq!(sum.num_runs, [2, 1, 1, 1]);
+        assert_eq!(sum.longest, [5, 4, 5, 5]);
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy([]), 0.0);
+        assert_eq!(shannon_entropy([0, 5]), 0.0);
+        assert!(shannon_entropy([5]).is_sign_positive());
+        assert_eq!(shannon_entropy([3, 3]), 1.0);
+        assert_eq!(shannon_entropy([1, 1, 1, 1]), 2.0);
+        assert!((shannon_entropy([1, 3]) - 0.811278).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_kmer_entropy() {
+        let mut counter = EntropyCounter::new(1);
+        assert_eq!(counter.entropy(b"AAAAAAAA"), 0.0);
+        assert_eq!(counter.entropy(b"ACACACAC"), 1.0);
+        assert_eq!(counter.entropy(b"acgtNNACGT"), 2.0);
+        assert_eq!(counter.entropy(b"NNNN"), 0.0);
+        // counts of the previous sequences are reset
+        assert_eq!(counter.entropy(b"AAAAAAAA"), 0.0);
+        let mut counter = EntropyCounter::new(2);
+        // dinucleotide repeat: AC and CA
+        assert_eq!(counter.entropy(b"ACACACACA"), 1.0);
+        // k-mers across N are skipped
+        assert_eq!(counter.entropy(b"ACNAC"), 0.0);
+    }
+
+    #[test]
+    fn test_entropy_stats() {
+        let stats = calc_entropy_stats(&[2.0, 1.0, 0.0], 1, 1.5);
+        assert_eq!(stats.mean, 1.0);
+        assert_eq!(stats.num_low_entropy, 2);
+        assert_eq!(calc_entropy_stats(&[], 1, 1.5).mean, 0.0);
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        assert_eq!(reverse_complement(b"TTAGGGn"), b"NCCCTAA".to_vec());
+        assert_eq!(reverse_complement(b""), b"".to_vec());
+        assert_eq!(reverse_complement(b"ryKMbvDHsw"), b"WSDHBVKMRY".to_vec());
+    }
 }
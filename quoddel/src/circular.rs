@@ -0,0 +1,119 @@
+/// Header tokens assemblers use to mark circular contigs
+/// (Flye/Unicycler `circular=true`, Canu `suggestCircular=yes`), case-insensitive.
+const CIRCULAR_TAGS: &[&str] = &["circular=true", "circular=yes", "suggestcircular=yes"];
+
+/// ID suffix of circular contigs in SPAdes-style names, e.g. plasmidSPAdes
+/// `NODE_1_length_5000_cov_10.5_type_circular`.
+const CIRCULAR_SUFFIX: &str = "_circular";
+
+/// Evidence for a circular contig.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Circularity {
+    pub header_tag: bool,
+    pub end_overlap: bool,
+}
+
+impl Circularity {
+    pub fn is_circular(&self) -> bool {
+        self.header_tag || self.end_overlap
+    }
+}
+
+/// Number and total length of circular contigs of one assembly.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct CircularStats {
+    pub num_contigs: usize,
+    pub total_length: usize,
+}
+
+impl CircularStats {
+    pub fn add(&mut self, circularity: Circularity, seqlen: usize) {
+        if circularity.is_circular() {
+            self.num_contigs += 1;
+            self.total_length += seqlen;
+        }
+    }
+}
+
+/// Whether the ID or the rest of the header line marks the contig as circular.
+pub fn has_circular_tag(id: &str, desc: Option<&str>) -> bool {
+    id.to_ascii_lowercase().ends_with(CIRCULAR_SUFFIX)
+        || desc
+            .into_iter()
+            .flat_map(|desc| desc.split_whitespace())
+            .any(|token| {
+                CIRCULAR_TAGS
+                    .iter()
+                    .any(|tag| token.eq_ignore_ascii_case(tag))
+            })
+}
+
+/// Whether the contig starts and ends with the same `k` bases (A, C, G and T only,
+/// case-insensitive), as left by assemblers that do not trim the overlap of circular contigs.
+/// Contigs shorter than `2 * k` are not checked.
+pub fn has_end_overlap(seq: &[u8], k: usize) -> bool {
+    if k == 0 || seq.len() < 2 * k {
+        return false;
+    }
+    let start = &seq[..k];
+    start
+        .iter()
+        .all(|c| matches!(c.to_ascii_uppercase(), b'A' | b'C' | b'G' | b'T'))
+        && start.eq_ignore_ascii_case(&seq[seq.len() - k..])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circular::{has_circular_tag, has_end_overlap, CircularStats, Circularity};
+
+    #[test]
+    fn test_circular_tag() {
+        assert!(has_circular_tag(
+            "1",
+            Some("length=5000 depth=1.00x circular=true")
+        ));
+        assert!(has_circular_tag(
+            "tig1",
+            Some("len=100 suggestCircular=yes")
+        ));
+        assert!(has_circular_tag(
+            "NODE_1_length_5000_cov_10.5_type_circular",
+            None
+        ));
+        assert!(!has_circular_tag("1", Some("length=5000 circular=false")));
+        assert!(!has_circular_tag("circular_1", None));
+        assert!(!has_circular_tag("contig_1", None));
+    }
+
+    #[test]
+    fn test_end_overlap() {
+        assert!(has_end_overlap(b"ACGTTTTTTacgt", 4));
+        assert!(!has_end_overlap(b"ACGTTTTTTACGA", 4));
+        assert!(!has_end_overlap(b"NNNNTTTTTNNNN", 4));
+        // too short for two k-mers
+        assert!(!has_end_overlap(b"ACGTACG", 4));
+        assert!(!has_end_overlap(b"", 0));
+    }
+
+    #[test]
+    fn test_circular_stats() {
+        let mut stats = CircularStats::default();
+        stats.add(
+            Circularity {
+                header_tag: true,
+                end_overlap: false,
+            },
+            100,
+        );
+        stats.add(Circularity::default(), 50);
+        stats.add(
+            Circularity {
+                header_tag: false,
+                end_overlap: true,
+            },
+            20,
+        );
+        assert_eq!(stats.num_contigs, 2);
+        assert_eq!(stats.total_length, 120);
+    }
+}
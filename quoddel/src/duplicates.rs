@@ -0,0 +1,134 @@
+use crate::calc::{complement, reverse_complement};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hasher;
+
+/// 128-bit hash of the canonical (lexicographically smaller of forward and reverse complement)
+/// sequence, case-insensitive, and whether the canonical sequence is the reverse complement.
+/// Two differently seeded 64-bit hashes keep accidental collisions out of assembly-sized inputs.
+pub fn canonical_hash(seq: &[u8]) -> (u128, bool) {
+    let forward = || seq.iter().map(|c| c.to_ascii_uppercase());
+    let reverse = || seq.iter().rev().map(|&c| complement(c));
+    let is_reverse = reverse().lt(forward());
+    let canonical = if is_reverse {
+        reverse_complement(seq)
+    } else {
+        seq.to_ascii_uppercase()
+    };
+    let mut hash: u128 = 0;
+    for seed in 0..2u8 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u8(seed);
+        hasher.write(&canonical);
+        hash = (hash << 64) | hasher.finish() as u128;
+    }
+    (hash, is_reverse)
+}
+
+/// A contig that is an exact or reverse-complement copy of other contigs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateContig {
+    pub name: String,
+    pub length: usize,
+    /// Reverse complement of the first contig of the group.
+    pub reverse: bool,
+}
+
+/// Duplicate contigs of one assembly.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DuplicateStats {
+    pub num_groups: usize,
+    /// Copies beyond the first contig of each group.
+    pub num_duplicates: usize,
+    /// Total length of the copies beyond the first contig of each group.
+    pub duplicated_length: usize,
+    /// Groups of at least two contigs, in order of first occurrence.
+    pub groups: Vec<Vec<DuplicateContig>>,
+}
+
+/// Groups contigs by the 128-bit hash of their canonical sequence while streaming.
+#[derive(Debug, Default)]
+pub struct DuplicateFinder {
+    group_index: HashMap<u128, usize>,
+    /// (name, length, canonical is reverse complement)
+    groups: Vec<Vec<(String, usize, bool)>>,
+}
+
+impl DuplicateFinder {
+    /// Empty sequences are skipped.
+    pub fn add(&mut self, name: &str, seq: &[u8]) {
+        if seq.is_empty() {
+            return;
+        }
+        let (hash, reverse) = canonical_hash(seq);
+        let groups = &mut self.groups;
+        let i = *self.group_index.entry(hash).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[i].push((name.to_string(), seq.len(), reverse));
+    }
+
+    pub fn finish(self) -> DuplicateStats {
+        let mut stats = DuplicateStats::default();
+        for group in self.groups.into_iter().filter(|g| g.len() > 1) {
+            let first_reverse = group[0].2;
+            stats.num_groups += 1;
+            stats.num_duplicates += group.len() - 1;
+            stats.duplicated_length += group[1..].iter().map(|c| c.1).sum::<usize>();
+            stats.groups.push(
+                group
+                    .into_iter()
+                    .map(|(name, length, reverse)| DuplicateContig {
+                        name,
+                        length,
+                        reverse: reverse != first_reverse,
+                    })
+                    .collect(),
+            );
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::duplicates::{canonical_hash, DuplicateFinder};
+
+    #[test]
+    fn test_canonical_hash() {
+        let (hash, reverse) = canonical_hash(b"AACGGT");
+        assert!(!reverse);
+        assert_eq!(canonical_hash(b"accgtt"), (hash, true));
+        assert_eq!(canonical_hash(b"AACGGT"), canonical_hash(b"aacggt"));
+        assert_ne!(canonical_hash(b"AACGGA").0, hash);
+        // palindrome
+        assert!(!canonical_hash(b"ACGT").1);
+        // ambiguity codes are complemented as well
+        assert_eq!(canonical_hash(b"AARYGT").0, canonical_hash(b"ACRYTT").0);
+        assert_ne!(canonical_hash(b"AARYGT").0, canonical_hash(b"ACYRTT").0);
+    }
+
+    #[test]
+    fn test_duplicate_finder() {
+        let mut finder = DuplicateFinder::default();
+        finder.add("a", b"ACCGTTA");
+        finder.add("b", b"GGGG");
+        finder.add("c", b"TAACGGT");
+        finder.add("d", b"accgtta");
+        finder.add("e", b"");
+        finder.add("f", b"");
+        finder.add("g", b"CCCC");
+        let stats = finder.finish();
+        assert_eq!(stats.num_groups, 2);
+        assert_eq!(stats.num_duplicates, 3);
+        assert_eq!(stats.duplicated_length, 18);
+        let group: Vec<(&str, bool)> = stats.groups[0]
+            .iter()
+            .map(|c| (c.name.as_str(), c.reverse))
+            .collect();
+        assert_eq!(group, vec![("a", false), ("c", true), ("d", false)]);
+        assert_eq!(stats.groups[1][1].name, "g");
+        assert!(stats.groups[1][1].reverse);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::calc::base_index;
+
+/// Triplets containing N or ambiguity codes are not counted.
+fn triplet_index(seq: &[u8], i: usize) -> Option<usize> {
+    Some(base_index(seq[i])? * 16 + base_index(seq[i + 1])? * 4 + base_index(seq[i + 2])?)
+}
+
+/// Low-complexity intervals (start, end exclusive) with the DUST triplet score:
+/// a window of `window` bases with l triplets is low-complexity if
+/// sum(c_t * (c_t - 1) / 2) / (l - 1) > `threshold` over the triplet counts c_t.
+/// Overlapping windows are merged and, as in sdust, both ends of each interval are trimmed
+/// to the triplets that raise the score. Sequences shorter than `window` are scored as one window.
+pub fn find_low_complexity(seq: &[u8], window: usize, threshold: usize) -> Vec<(usize, usize)> {
+    let mut intervals: Vec<(usize, usize)> = Vec::new();
+    let window = window.min(seq.len());
+    if window < 4 {
+        return intervals;
+    }
+    let num_triplets = window - 2;
+    let mut counts = [0usize; 64];
+    // sum of c_t * (c_t - 1) / 2, updated as triplets enter and leave the window
+    let mut score: usize = 0;
+    for i in 0..num_triplets {
+        if let Some(t) = triplet_index(seq, i) {
+            score += counts[t];
+            counts[t] += 1;
+        }
+    }
+    for start in 0..=seq.len() - window {
+        if start > 0 {
+            if let Some(t) = triplet_index(seq, start - 1) {
+                counts[t] -= 1;
+                score -= counts[t];
+            }
+            if let Some(t) = triplet_index(seq, start + num_triplets - 1) {
+                score += counts[t];
+                counts[t] += 1;
+            }
+        }
+        if score > threshold * (num_triplets - 1) {
+            let end = start + window;
+            match intervals.last_mut() {
+                Some(last) if last.1 >= start => last.1 = end,
+                _ => intervals.push((start, end)),
+            }
+        }
+    }
+    let triplets = |start: usize, end: usize| -> Vec<Option<usize>> {
+        (start..end - 2).map(|i| triplet_index(seq, i)).collect()
+    };
+    intervals
+        .into_iter()
+        .map(|(start, end)| {
+            let start = start + best_trim(&triplets(start, end.min(start + window)));
+            let mut last_window = triplets(start.max(end - window), end);
+            last_window.reverse();
+            (start, end - best_trim(&last_window))
+        })
+        .collect()
+}
+
+/// Number of triplets to drop from the front of a window for the highest score of the rest.
+fn best_trim(triplets: &[Option<usize>]) -> usize {
+    let mut counts = [0usize; 64];
+    let mut score: usize = 0;
+    for &t in triplets.iter().flatten() {
+        score += counts[t];
+        counts[t] += 1;
+    }
+    let (mut best, mut best_score, mut best_len) = (0, score, triplets.len());
+    for (i, t) in triplets.iter().enumerate() {
+        let len = triplets.len() - i - 1;
+        if len < 2 {
+            break;
+        }
+        if let Some(t) = *t {
+            counts[t] -= 1;
+            score -= counts[t];
+        }
+        // score / (len - 1) > best_score / (best_len - 1)
+        if score * (best_len - 1) > best_score * (len - 1) {
+            best = i + 1;
+            best_score = score;
+            best_len = len;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dust::find_low_complexity;
+
+    /// Deterministic pseudo-random bases.
+    fn random_seq(length: usize, mut state: u32) -> Vec<u8> {
+        (0..length)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                b"ACGT"[(state % 4) as usize]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_low_complexity_homopolymer() {
+        assert_eq!(find_low_complexity(&[b'A'; 100], 64, 20), vec![(0, 100)]);
+        // too short for a score above 20
+        assert_eq!(find_low_complexity(&[b'A'; 30], 64, 20), vec![]);
+        assert_eq!(find_low_complexity(&[b'A'; 30], 64, 2), vec![(0, 30)]);
+        assert_eq!(find_low_complexity(&[b'N'; 100], 64, 20), vec![]);
+        assert_eq!(find_low_complexity(b"", 64, 20), vec![]);
+    }
+
+    #[test]
+    fn test_low_complexity_random() {
+        let random = random_seq(1000, 42);
+        assert_eq!(find_low_complexity(&random, 64, 20), vec![]);
+        let seq = [random_seq(200, 1), vec![b'T'; 100], random_seq(200, 2)].concat();
+        let intervals = find_low_complexity(&seq, 64, 20);
+        assert_eq!(intervals.len(), 1);
+        let (start, end) = intervals[0];
+        // trimmed to the poly-T, give or take a matching flanking base
+        assert!((195..=200).contains(&start));
+        assert!((300..=305).contains(&end));
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::fmt;
+
+/// Characters that break downstream tools when part of a sequence ID.
+const PROBLEMATIC_CHARACTERS: &[char] = &['|', ':'];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IdIssue {
+    /// ID seen before in the same file.
+    Duplicate,
+    /// Empty or whitespace-only ID.
+    Empty,
+    /// '|', ':', whitespace, control or non-ASCII characters.
+    ProblematicCharacters,
+}
+
+impl fmt::Display for IdIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let issue = match self {
+            IdIssue::Duplicate => "duplicate",
+            IdIssue::Empty => "empty",
+            IdIssue::ProblematicCharacters => "problematic characters",
+        };
+        write!(f, "{}", issue)
+    }
+}
+
+/// Problems with the sequence IDs of one fasta file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IdStats {
+    /// Sequences whose ID was used by an earlier sequence.
+    pub num_duplicate: usize,
+    pub num_empty: usize,
+    pub num_problematic: usize,
+    /// (ID, issue) in file order.
+    pub issues: Vec<(String, IdIssue)>,
+}
+
+/// Checks sequence IDs while streaming.
+#[derive(Debug, Default)]
+pub struct IdChecker {
+    seen: HashSet<String>,
+    stats: IdStats,
+}
+
+impl IdChecker {
+    pub fn add(&mut self, id: &str) {
+        let stats = &mut self.stats;
+        if id.trim().is_empty() {
+            stats.num_empty += 1;
+            stats.issues.push((id.to_string(), IdIssue::Empty));
+            return;
+        }
+        if !self.seen.insert(id.to_string()) {
+            stats.num_duplicate += 1;
+            stats.issues.push((id.to_string(), IdIssue::Duplicate));
+        }
+        if id
+            .chars()
+            .any(|c| PROBLEMATIC_CHARACTERS.contains(&c) || !c.is_ascii_graphic())
+        {
+            stats.num_problematic += 1;
+            stats
+                .issues
+                .push((id.to_string(), IdIssue::ProblematicCharacters));
+        }
+    }
+
+    pub fn finish(self) -> IdStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids::{IdChecker, IdIssue};
+
+    #[test]
+    fn test_id_checker() {
+        let mut checker = IdChecker::default();
+        for id in [
+            "ctg1",
+            "ctg2",
+            "ctg1",
+            "",
+            " \t",
+            "gi|123",
+            "chr1:1-100",
+            "contig_ä",
+            "ctg1",
+        ] {
+            checker.add(id);
+        }
+        let stats = checker.finish();
+        assert_eq!(stats.num_duplicate, 2);
+        assert_eq!(stats.num_empty, 2);
+        assert_eq!(stats.num_problematic, 3);
+        assert_eq!(
+            stats.issues,
+            vec![
+                ("ctg1".to_string(), IdIssue::Duplicate),
+                ("".to_string(), IdIssue::Empty),
+                (" \t".to_string(), IdIssue::Empty),
+                ("gi|123".to_string(), IdIssue::ProblematicCharacters),
+                ("chr1:1-100".to_string(), IdIssue::ProblematicCharacters),
+                ("contig_ä".to_string(), IdIssue::ProblematicCharacters),
+                ("ctg1".to_string(), IdIssue::Duplicate),
+            ]
+        );
+    }
+}
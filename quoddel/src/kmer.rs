@@ -0,0 +1,153 @@
+use crate::calc::base_index;
+use std::collections::HashMap;
+
+pub const MAX_K: usize = 31;
+
+/// Default cap on the number of distinct k-mers, about 32 bytes each in the hash map.
+pub const DEFAULT_MAX_DISTINCT_KMERS: usize = 10_000_000;
+
+/// Counts canonical k-mers (k <= 31), packed into 2 bits per base.
+/// k-mers containing N or ambiguity codes are skipped. To bound memory, counting
+/// stops for good at the first new k-mer beyond `max_distinct` distinct k-mers.
+#[derive(Debug)]
+pub struct KmerCounter {
+    k: usize,
+    max_distinct: usize,
+    capped: bool,
+    counts: HashMap<u64, u32>,
+}
+
+/// k-mer spectrum of one assembly.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KmerStats {
+    pub k: usize,
+    pub num_kmers: usize,
+    pub num_distinct: usize,
+    /// Distinct k-mers that occur more than once.
+    pub num_repeated: usize,
+    /// (multiplicity, number of distinct k-mers), sorted by multiplicity.
+    pub histogram: Vec<(u32, usize)>,
+    /// Counting stopped at the cap, the stats only cover the k-mers before.
+    pub capped: bool,
+}
+
+impl KmerCounter {
+    pub fn new(k: usize, max_distinct: usize) -> Self {
+        assert!(
+            (1..=MAX_K).contains(&k),
+            "k must be between 1 and {}",
+            MAX_K
+        );
+        KmerCounter {
+            k,
+            max_distinct,
+            capped: false,
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add_sequence(&mut self, seq: &[u8]) {
+        if self.capped {
+            return;
+        }
+        let k = self.k;
+        let mask: u64 = (1 << (2 * k)) - 1;
+        let mut forward: u64 = 0;
+        let mut reverse: u64 = 0;
+        let mut valid: usize = 0;
+        for &nuc in seq {
+            match base_index(nuc) {
+                Some(b) => {
+                    let b = b as u64;
+                    forward = ((forward << 2) | b) & mask;
+                    reverse = (reverse >> 2) | ((3 - b) << (2 * (k - 1)));
+                    valid += 1;
+                    if valid >= k {
+                        let kmer = forward.min(reverse);
+                        if let Some(count) = self.counts.get_mut(&kmer) {
+                            *count = count.saturating_add(1);
+                        } else if self.counts.len() < self.max_distinct {
+                            self.counts.insert(kmer, 1);
+                        } else {
+                            self.capped = true;
+                            return;
+                        }
+                    }
+                }
+                None => valid = 0,
+            }
+        }
+    }
+
+    pub fn stats(&self) -> KmerStats {
+        let mut histogram: HashMap<u32, usize> = HashMap::new();
+        for &count in self.counts.values() {
+            *histogram.entry(count).or_insert(0) += 1;
+        }
+        let mut histogram: Vec<(u32, usize)> = histogram.into_iter().collect();
+        histogram.sort_unstable();
+        KmerStats {
+            k: self.k,
+            num_kmers: self.counts.values().map(|&c| c as usize).sum(),
+            num_distinct: self.counts.len(),
+            num_repeated: self.counts.values().filter(|&&c| c > 1).count(),
+            histogram,
+            capped: self.capped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kmer::KmerCounter;
+
+    #[test]
+    fn test_canonical_kmers() {
+        let mut counter = KmerCounter::new(3, 100);
+        // ACG and its reverse complement CGT are the same canonical k-mer
+        counter.add_sequence(b"ACGT");
+        let stats = counter.stats();
+        assert_eq!(stats.num_kmers, 2);
+        assert_eq!(stats.num_distinct, 1);
+        assert_eq!(stats.num_repeated, 1);
+        assert_eq!(stats.histogram, vec![(2, 1)]);
+    }
+
+    #[test]
+    fn test_kmers_skip_n() {
+        let mut counter = KmerCounter::new(2, 100);
+        counter.add_sequence(b"AANAAcNT");
+        counter.add_sequence(b"tt");
+        let stats = counter.stats();
+        // AA, AA, AC, TT (= AA)
+        assert_eq!(stats.num_kmers, 4);
+        assert_eq!(stats.num_distinct, 2);
+        assert_eq!(stats.histogram, vec![(1, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn test_max_k() {
+        let seq = b"ACGGTCATTGACCTAGGATCCAAGTTCAGCATGTACCGAT";
+        let reverse_complement = b"ATCGGTACATGCTGAACTTGGATCCTAGGTCAATGACCGT";
+        let mut counter = KmerCounter::new(31, 100);
+        counter.add_sequence(seq);
+        counter.add_sequence(reverse_complement);
+        let stats = counter.stats();
+        assert_eq!(stats.num_kmers, 2 * (seq.len() - 30));
+        assert_eq!(stats.num_distinct, seq.len() - 30);
+        assert_eq!(stats.histogram, vec![(2, seq.len() - 30)]);
+        assert!(!stats.capped);
+    }
+
+    #[test]
+    fn test_max_distinct() {
+        let mut counter = KmerCounter::new(2, 2);
+        // AC, CC, then CA is a third distinct 2-mer and nothing is counted after
+        counter.add_sequence(b"ACCAT");
+        counter.add_sequence(b"AC");
+        let stats = counter.stats();
+        assert!(stats.capped);
+        assert_eq!(stats.num_distinct, 2);
+        assert_eq!(stats.num_kmers, 2);
+    }
+}
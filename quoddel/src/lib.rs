@@ -1,20 +1,165 @@
 pub mod calc;
+pub mod circular;
+pub mod duplicates;
+pub mod dust;
+pub mod ids;
+pub mod kmer;
+pub mod orf;
 pub mod output;
+pub mod protein;
+pub mod telomere;
 
 use clap::{Arg, Command};
-use seq_io::fasta::Reader;
+use seq_io::fasta::{Reader, Record};
 use std::error::Error;
+use std::io::Write;
 
 use crate::calc::*;
-use crate::output::FastaInfo;
+use crate::circular::{has_circular_tag, has_end_overlap, CircularStats, Circularity};
+use crate::duplicates::DuplicateFinder;
+use crate::dust::find_low_complexity;
+use crate::ids::IdChecker;
+use crate::kmer::{KmerCounter, DEFAULT_MAX_DISTINCT_KMERS, MAX_K};
+use crate::orf::{calc_orf_stats, longest_orf};
+use crate::output::*;
+use crate::protein::{detect_alphabet, Alphabet, ProteinBuilder};
+use crate::telomere::{telomere_ends, TelomereStats};
 
 type QuoddelResult<T> = Result<T, Box<dyn Error>>;
 
-#[derive(Debug)]
+/// Options of a quoddel run. Library users can start from `Config::default()`,
+/// which matches the command line defaults:
+///
+/// ```
+/// use quoddel::{read_fasta_sequences, Config};
+/// use seq_io::fasta::Reader;
+///
+/// let config = Config {
+///     min_contig_length: 0,
+///     ..Config::default()
+/// };
+/// let reader = Reader::new(&b">a\nACGT\n>b\nAC\n"[..]);
+/// let info = read_fasta_sequences(String::from("example"), &config, reader)?;
+/// assert!((info.aun() - 20.0 / 6.0).abs() < 1e-9);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
 pub struct Config {
-    files: Vec<String>, //...
-    min_contig_length: usize,
-    debug: bool,
+    pub files: Vec<String>, //...
+    pub min_contig_length: usize,
+    pub alphabet: Alphabet,
+    pub est_genome_size: Option<usize>,
+    pub nx_percentages: Vec<usize>,
+    pub nx_curve: Option<String>,
+    pub length_thresholds: Vec<usize>,
+    pub length_histogram: Option<String>,
+    /// Lower bounds of the length histogram bins, log10 bins if None.
+    pub length_bins: Option<Vec<usize>>,
+    /// Split scaffolds at runs of at least this many Ns.
+    pub min_gap: Option<usize>,
+    pub gc_options: GcOptions,
+    pub gc_histogram: Option<String>,
+    /// Count dinucleotides and report the CpG o/e ratio.
+    pub cpg: bool,
+    pub dinucleotides: Option<String>,
+    pub kmer_size: Option<usize>,
+    pub kmer_histogram: Option<String>,
+    /// Stop counting k-mers beyond this many distinct k-mers.
+    pub kmer_max_distinct: usize,
+    pub min_homopolymer: usize,
+    /// Compute the Shannon entropy of each contig.
+    pub entropy: bool,
+    pub entropy_k: usize,
+    pub min_entropy: f64,
+    /// Find DUST low-complexity intervals.
+    pub low_complexity: bool,
+    pub dust_window: usize,
+    pub dust_threshold: usize,
+    pub low_complexity_bed: Option<String>,
+    /// Group identical and reverse-complement identical contigs.
+    pub find_duplicates: bool,
+    pub duplicates: Option<String>,
+    pub id_issues: Option<String>,
+    /// Detect circular contigs by header tag and end overlap.
+    pub circular: bool,
+    /// Length of the identical start and end of circular contigs.
+    pub circular_overlap: usize,
+    pub circular_table: Option<String>,
+    pub per_contig: Option<String>,
+    /// Minimum ORF length (aa) to report the fraction of contigs with such an ORF.
+    pub min_orf: Option<usize>,
+    /// Motif and window size to scan contig ends for telomere repeats.
+    pub telomeres: Option<(String, usize)>,
+    pub telomere_table: Option<String>,
+    pub debug: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            files: vec![String::from("-")],
+            min_contig_length: 500,
+            alphabet: Alphabet::Auto,
+            est_genome_size: None,
+            nx_percentages: vec![50, 90],
+            nx_curve: None,
+            length_thresholds: vec![0, 1_000, 5_000, 10_000, 25_000, 50_000],
+            length_histogram: None,
+            length_bins: None,
+            min_gap: None,
+            gc_options: GcOptions::default(),
+            gc_histogram: None,
+            cpg: false,
+            dinucleotides: None,
+            kmer_size: None,
+            kmer_histogram: None,
+            kmer_max_distinct: DEFAULT_MAX_DISTINCT_KMERS,
+            min_homopolymer: 5,
+            entropy: false,
+            entropy_k: 1,
+            min_entropy: 1.5,
+            low_complexity: false,
+            dust_window: 64,
+            dust_threshold: 20,
+            low_complexity_bed: None,
+            find_duplicates: false,
+            duplicates: None,
+            id_issues: None,
+            circular: false,
+            circular_overlap: 31,
+            circular_table: None,
+            per_contig: None,
+            min_orf: None,
+            telomeres: None,
+            telomere_table: None,
+            debug: false,
+        }
+    }
+}
+
+impl Config {
+    /// Options for the contigs of split scaffolds. Analyses that give the same result as
+    /// for the scaffolds, or only make sense for the input sequences, are turned off.
+    fn for_broken(&self) -> Config {
+        Config {
+            min_gap: None,
+            cpg: false,
+            dinucleotides: None,
+            kmer_size: None,
+            entropy: false,
+            low_complexity: false,
+            low_complexity_bed: None,
+            find_duplicates: false,
+            duplicates: None,
+            circular: false,
+            circular_table: None,
+            per_contig: None,
+            min_orf: None,
+            telomeres: None,
+            telomere_table: None,
+            ..self.clone()
+        }
+    }
 }
 
 pub fn get_args() -> QuoddelResult<Config> {
@@ -31,37 +176,550 @@ pub fn get_args() -> QuoddelResult<Config> {
             .long("min-contig")
             .help("minimum contig length to be considered for some stats (to be compatible with QUAST output)")
             .default_value("500")
+    ).arg(
+        Arg::new("alphabet")
+            .long("alphabet")
+            .takes_value(true)
+            .possible_values(["auto", "nucleotide", "protein"])
+            .help("sequence type, auto-detected from the first sequence of each file by default; protein stats ignore the length cutoff")
+            .default_value("auto")
+    ).arg(
+        Arg::new("est_genome_size").short('g')
+            .long("est-ref-size")
+            .takes_value(true)
+            .help("estimated genome size for NGx/LGx, either a number with optional k/M/G suffix (e.g. 3.1G) or a reference fasta file")
+    ).arg(
+        Arg::new("nx_percentages")
+            .long("nx")
+            .takes_value(true)
+            .help("comma-separated percentages to report Nx/Lx (and NGx/LGx) for")
+            .default_value("50,90")
+    ).arg(
+        Arg::new("nx_curve")
+            .long("nx-curve")
+            .takes_value(true)
+            .help("write the Nx (and NGx) curve for x = 0..100 of all assemblies to this file")
+    ).arg(
+        Arg::new("length_thresholds")
+            .long("thresholds")
+            .takes_value(true)
+            .help("comma-separated contig lengths for the num contigs / total length (>= x bp) rows, with optional k/M/G suffix")
+            .default_value("0,1000,5000,10000,25000,50000")
+    ).arg(
+        Arg::new("length_histogram")
+            .long("length-histogram")
+            .takes_value(true)
+            .help("write the contig length histogram (log10 bins unless --length-bins is given) of all assemblies to this file")
+    ).arg(
+        Arg::new("length_bins")
+            .long("length-bins")
+            .takes_value(true)
+            .requires("length_histogram")
+            .help("comma-separated lower bounds of the length histogram bins, with optional k/M/G suffix")
+    ).arg(
+        Arg::new("scaffolds").short('s')
+            .long("scaffolds")
+            .takes_value(false)
+            .help("input contains scaffolds: also report contig-level stats by breaking sequences at gaps")
+    ).arg(
+        Arg::new("min_gap")
+            .long("min-gap")
+            .takes_value(true)
+            .help("minimum number of consecutive Ns treated as scaffold gap with --scaffolds")
+            .default_value("10")
+    ).arg(
+        Arg::new("gc_count_s")
+            .long("gc-count-s")
+            .takes_value(false)
+            .help("count ambiguity code S (G or C) as GC and W (A or T) as AT")
+    ).arg(
+        Arg::new("gc_ambiguous")
+            .long("gc-ambiguous")
+            .takes_value(false)
+            .help("include the remaining ambiguity codes (R, Y, K, M, B, D, H, V) in the GC (%) denominator")
+    ).arg(
+        Arg::new("gc_histogram")
+            .long("gc-histogram")
+            .takes_value(true)
+            .help("write the per-contig GC (%) histogram (1% bins) of all assemblies to this file")
+    ).arg(
+        Arg::new("cpg")
+            .long("cpg")
+            .takes_value(false)
+            .help("count dinucleotides and report the CpG observed/expected ratio")
+    ).arg(
+        Arg::new("dinucleotides")
+            .long("dinucleotides")
+            .takes_value(true)
+            .help("write per-contig dinucleotide counts and CpG o/e of all assemblies to this file")
+    ).arg(
+        Arg::new("kmer_size").short('k')
+            .long("kmer-size")
+            .takes_value(true)
+            .help("count canonical k-mers of this size (up to 31) and report the k-mer spectrum")
+    ).arg(
+        Arg::new("kmer_histogram")
+            .long("kmer-histogram")
+            .takes_value(true)
+            .requires("kmer_size")
+            .help("write the k-mer multiplicity histogram of all assemblies to this file")
+    ).arg(
+        Arg::new("kmer_max_distinct")
+            .long("kmer-max-distinct")
+            .takes_value(true)
+            .help("stop counting k-mers once this many distinct k-mers (about 32 bytes each) are stored, with optional k/M/G suffix")
+            .default_value("10M")
+    ).arg(
+        Arg::new("min_homopolymer")
+            .long("min-homopolymer")
+            .takes_value(true)
+            .help("minimum length of homopolymer runs to be reported")
+            .default_value("5")
+    ).arg(
+        Arg::new("entropy")
+            .long("entropy")
+            .takes_value(false)
+            .help("compute the Shannon entropy of each contig and count low-entropy contigs")
+    ).arg(
+        Arg::new("entropy_k")
+            .long("entropy-k")
+            .takes_value(true)
+            .help("compute the entropy with --entropy from k-mers of this size (up to 8), 1 for bases")
+            .default_value("1")
+    ).arg(
+        Arg::new("min_entropy")
+            .long("min-entropy")
+            .takes_value(true)
+            .help("contigs with an entropy below this (in bits, at most 2k) are counted as low-entropy")
+            .default_value("1.5")
+    ).arg(
+        Arg::new("low_complexity")
+            .long("low-complexity")
+            .takes_value(false)
+            .help("find low-complexity intervals with the DUST triplet score")
+    ).arg(
+        Arg::new("dust_window")
+            .long("dust-window")
+            .takes_value(true)
+            .help("window size for the DUST low-complexity score with --low-complexity")
+            .default_value("64")
+    ).arg(
+        Arg::new("dust_threshold")
+            .long("dust-threshold")
+            .takes_value(true)
+            .help("windows with a DUST score above this are low-complexity with --low-complexity")
+            .default_value("20")
+    ).arg(
+        Arg::new("low_complexity_bed")
+            .long("low-complexity-bed")
+            .takes_value(true)
+            .requires("low_complexity")
+            .help("write the low-complexity intervals to this BED file, numbered per assembly (e.g. lc.1.bed, lc.2.bed) for several input files")
+    ).arg(
+        Arg::new("find_duplicates")
+            .long("find-duplicates")
+            .takes_value(false)
+            .help("find identical or reverse-complement identical contigs")
+    ).arg(
+        Arg::new("duplicates")
+            .long("duplicates")
+            .takes_value(true)
+            .requires("find_duplicates")
+            .help("write groups of identical or reverse-complement identical contigs of all assemblies to this file")
+    ).arg(
+        Arg::new("id_issues")
+            .long("id-issues")
+            .takes_value(true)
+            .help("write duplicate, empty and problematic sequence IDs of all assemblies to this file")
+    ).arg(
+        Arg::new("circular")
+            .long("circular")
+            .takes_value(false)
+            .help("detect circular contigs by header tag and end overlap")
+    ).arg(
+        Arg::new("circular_overlap")
+            .long("circular-overlap")
+            .takes_value(true)
+            .help("with --circular, contigs that start and end with the same bases of this length are reported as circular")
+            .default_value("31")
+    ).arg(
+        Arg::new("circular_table")
+            .long("circular-table")
+            .takes_value(true)
+            .requires("circular")
+            .help("write whether each contig of all assemblies is circular, by header tag or end overlap, to this file")
+    ).arg(
+        Arg::new("per_contig")
+            .long("per-contig")
+            .takes_value(true)
+            .help("write length and composition of each sequence of all assemblies, including those below the length cutoff, sorted by length, to this file")
+    ).arg(
+        Arg::new("orfs")
+            .long("orfs")
+            .takes_value(false)
+            .help("find the longest ORF in all six frames of each contig, e.g. for transcriptome assemblies")
+    ).arg(
+        Arg::new("min_orf")
+            .long("min-orf")
+            .takes_value(true)
+            .help("report the fraction of contigs with an ORF of at least this many amino acids with --orfs")
+            .default_value("100")
+    ).arg(
+        Arg::new("telomeres")
+            .long("telomeres")
+            .takes_value(false)
+            .help("scan the ends of each contig for telomere repeats, an end counts as telomeric with at least 10 tandem copies of the motif")
+    ).arg(
+        Arg::new("telomere_motif")
+            .long("telomere-motif")
+            .takes_value(true)
+            .help("telomere repeat motif, also searched as reverse complement")
+            .default_value("TTAGGG")
+    ).arg(
+        Arg::new("telomere_window")
+            .long("telomere-window")
+            .takes_value(true)
+            .help("number of bases at each contig end to scan for telomere repeats, with optional k/M/G suffix")
+            .default_value("5k")
+    ).arg(
+        Arg::new("telomere_table")
+            .long("telomere-table")
+            .takes_value(true)
+            .requires("telomeres")
+            .help("write the telomere repeat lengths at both ends of each contig of all assemblies to this file")
     ).arg(Arg::new("debug").long("--debug").takes_value(false).help("print debug output to stdout"))
         .get_matches();
     let files = matches.values_of_lossy("files").unwrap();
     let min_contig_length = matches.value_of("min_contig_length").unwrap().parse()?;
+    let alphabet = match matches.value_of("alphabet").unwrap() {
+        "nucleotide" => Alphabet::Nucleotide,
+        "protein" => Alphabet::Protein,
+        _ => Alphabet::Auto,
+    };
+    let est_genome_size = match matches.value_of("est_genome_size") {
+        Some(size) => Some(parse_genome_size(size)?),
+        None => None,
+    };
+    let nx_percentages = parse_percentages(matches.value_of("nx_percentages").unwrap())?;
+    let nx_curve = matches.value_of("nx_curve").map(String::from);
+    let length_thresholds = parse_thresholds(matches.value_of("length_thresholds").unwrap())?;
+    let length_histogram = matches.value_of("length_histogram").map(String::from);
+    let length_bins = matches
+        .value_of("length_bins")
+        .map(parse_thresholds)
+        .transpose()?;
+    let min_gap = if matches.is_present("scaffolds") {
+        Some(matches.value_of("min_gap").unwrap().parse()?)
+    } else {
+        None
+    };
+    let gc_options = GcOptions {
+        count_s: matches.is_present("gc_count_s"),
+        ambiguous_in_denominator: matches.is_present("gc_ambiguous"),
+    };
+    let gc_histogram = matches.value_of("gc_histogram").map(String::from);
+    let cpg = matches.is_present("cpg");
+    let dinucleotides = matches.value_of("dinucleotides").map(String::from);
+    let kmer_size = match matches.value_of("kmer_size") {
+        Some(k) => match k.parse::<usize>() {
+            Ok(k) if (1..=MAX_K).contains(&k) => Some(k),
+            _ => return Err(format!("invalid k-mer size '{}', must be 1 to {}", k, MAX_K).into()),
+        },
+        None => None,
+    };
+    let kmer_histogram = matches.value_of("kmer_histogram").map(String::from);
+    let kmer_max_distinct = matches.value_of("kmer_max_distinct").unwrap();
+    let kmer_max_distinct = parse_size(kmer_max_distinct)
+        .ok_or_else(|| format!("invalid k-mer cap '{}'", kmer_max_distinct))?;
+    let min_homopolymer = matches.value_of("min_homopolymer").unwrap().parse()?;
+    let entropy = matches.is_present("entropy");
+    let entropy_k = match matches.value_of("entropy_k").unwrap().parse::<usize>() {
+        Ok(k) if (1..=MAX_ENTROPY_K).contains(&k) => k,
+        _ => {
+            return Err(format!(
+                "invalid entropy k-mer size '{}', must be 1 to {}",
+                matches.value_of("entropy_k").unwrap(),
+                MAX_ENTROPY_K
+            )
+            .into())
+        }
+    };
+    let min_entropy = matches.value_of("min_entropy").unwrap().parse()?;
+    let low_complexity = matches.is_present("low_complexity");
+    let dust_window = match matches.value_of("dust_window").unwrap().parse::<usize>() {
+        Ok(w) if w >= 4 => w,
+        _ => {
+            return Err(format!(
+                "invalid DUST window '{}', must be at least 4",
+                matches.value_of("dust_window").unwrap()
+            )
+            .into())
+        }
+    };
+    let dust_threshold = matches.value_of("dust_threshold").unwrap().parse()?;
+    let low_complexity_bed = matches.value_of("low_complexity_bed").map(String::from);
+    let find_duplicates = matches.is_present("find_duplicates");
+    let duplicates = matches.value_of("duplicates").map(String::from);
+    let id_issues = matches.value_of("id_issues").map(String::from);
+    let circular = matches.is_present("circular");
+    let circular_overlap = match matches
+        .value_of("circular_overlap")
+        .unwrap()
+        .parse::<usize>()
+    {
+        Ok(k) if k > 0 => k,
+        _ => {
+            return Err(format!(
+                "invalid circular overlap '{}'",
+                matches.value_of("circular_overlap").unwrap()
+            )
+            .into())
+        }
+    };
+    let circular_table = matches.value_of("circular_table").map(String::from);
+    let per_contig = matches.value_of("per_contig").map(String::from);
+    let min_orf = if matches.is_present("orfs") {
+        Some(matches.value_of("min_orf").unwrap().parse()?)
+    } else {
+        None
+    };
+    let telomeres = if matches.is_present("telomeres") {
+        let motif = matches.value_of("telomere_motif").unwrap();
+        if motif.is_empty() || !motif.bytes().all(|c| base_index(c).is_some()) {
+            return Err(format!("invalid telomere motif '{}'", motif).into());
+        }
+        let window = matches.value_of("telomere_window").unwrap();
+        let window =
+            parse_size(window).ok_or_else(|| format!("invalid telomere window '{}'", window))?;
+        Some((motif.to_string(), window))
+    } else {
+        None
+    };
+    let telomere_table = matches.value_of("telomere_table").map(String::from);
     let debug = matches.is_present("debug");
     Ok(Config {
         files,
         min_contig_length,
+        alphabet,
+        est_genome_size,
+        nx_percentages,
+        nx_curve,
+        length_thresholds,
+        length_histogram,
+        length_bins,
+        min_gap,
+        gc_options,
+        gc_histogram,
+        cpg,
+        dinucleotides,
+        kmer_size,
+        kmer_histogram,
+        kmer_max_distinct,
+        min_homopolymer,
+        entropy,
+        entropy_k,
+        min_entropy,
+        low_complexity,
+        dust_window,
+        dust_threshold,
+        low_complexity_bed,
+        find_duplicates,
+        duplicates,
+        id_issues,
+        circular,
+        circular_overlap,
+        circular_table,
+        per_contig,
+        min_orf,
+        telomeres,
+        telomere_table,
         debug,
     })
 }
 
+/// Parses a size like "5000", "100k", "3.1G".
+pub fn parse_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+    let (number, factor) = match size.chars().last()? {
+        'k' | 'K' => (&size[..size.len() - 1], 1_000.0),
+        'm' | 'M' => (&size[..size.len() - 1], 1_000_000.0),
+        'g' | 'G' => (&size[..size.len() - 1], 1_000_000_000.0),
+        _ => (size, 1.0),
+    };
+    if let Ok(n) = number.parse::<usize>() {
+        return n.checked_mul(factor as usize);
+    }
+    match number.parse::<f64>() {
+        Ok(n) if n >= 0.0 && (n * factor).round() < usize::MAX as f64 => {
+            Some((n * factor).round() as usize)
+        }
+        _ => None,
+    }
+}
+
+/// Parses a list like "10,25,50,75,90,95" into sorted, unique percentages.
+fn parse_percentages(list: &str) -> QuoddelResult<Vec<usize>> {
+    let mut percentages = Vec::new();
+    for value in list.split(',') {
+        match value.trim().parse::<usize>() {
+            Ok(p) if p <= 100 => percentages.push(p),
+            _ => return Err(format!("invalid Nx percentage '{}'", value).into()),
+        }
+    }
+    percentages.sort_unstable();
+    percentages.dedup();
+    Ok(percentages)
+}
+
+/// Parses a list like "0,1000,1M,10M" into sorted, unique lengths.
+fn parse_thresholds(list: &str) -> QuoddelResult<Vec<usize>> {
+    let mut thresholds = Vec::new();
+    for value in list.split(',') {
+        match parse_size(value) {
+            Some(t) => thresholds.push(t),
+            None => return Err(format!("invalid length threshold '{}'", value).into()),
+        }
+    }
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    Ok(thresholds)
+}
+
+/// Genome size given as number or as path to a reference fasta (total length of all records).
+fn parse_genome_size(size: &str) -> QuoddelResult<usize> {
+    let total = match parse_size(size) {
+        Some(n) => n,
+        None => {
+            let mut reader = Reader::from_path(size)
+                .map_err(|e| format!("invalid genome size '{}': {}", size, e))?;
+            let mut total: usize = 0;
+            while let Some(result) = reader.next() {
+                let record = result?;
+                total += record.seq_lines().fold(0, |l, seq| l + seq.len());
+            }
+            total
+        }
+    };
+    if total == 0 {
+        return Err(format!("invalid genome size '{}': must be greater than 0", size).into());
+    }
+    Ok(total)
+}
+
 pub fn run(config: Config) -> QuoddelResult<()> {
     if config.debug {
         println!("{:#?}", config);
     }
-    for file in config.files {
+    let mut nx_curve_out = config
+        .nx_curve
+        .as_ref()
+        .map(|path| create_table(path, &nx_curve_header(config.est_genome_size.is_some())))
+        .transpose()?;
+    let mut length_histogram_out = config
+        .length_histogram
+        .as_ref()
+        .map(|path| create_table(path, LENGTH_HISTOGRAM_HEADER))
+        .transpose()?;
+    let mut gc_histogram_out = config
+        .gc_histogram
+        .as_ref()
+        .map(|path| create_table(path, GC_HISTOGRAM_HEADER))
+        .transpose()?;
+    let mut dinucleotides_out = config
+        .dinucleotides
+        .as_ref()
+        .map(|path| create_table(path, &dinucleotides_header()))
+        .transpose()?;
+    let mut kmer_histogram_out = config
+        .kmer_histogram
+        .as_ref()
+        .map(|path| create_table(path, KMER_HISTOGRAM_HEADER))
+        .transpose()?;
+    let mut telomere_table_out = config
+        .telomere_table
+        .as_ref()
+        .map(|path| create_table(path, TELOMERE_TABLE_HEADER))
+        .transpose()?;
+    let mut duplicates_out = config
+        .duplicates
+        .as_ref()
+        .map(|path| create_table(path, DUPLICATES_HEADER))
+        .transpose()?;
+    let mut id_issues_out = config
+        .id_issues
+        .as_ref()
+        .map(|path| create_table(path, ID_ISSUES_HEADER))
+        .transpose()?;
+    let mut circular_table_out = config
+        .circular_table
+        .as_ref()
+        .map(|path| create_table(path, CIRCULAR_TABLE_HEADER))
+        .transpose()?;
+    let mut per_contig_out = config
+        .per_contig
+        .as_ref()
+        .map(|path| {
+            create_table(
+                path,
+                &per_contig_header(config.entropy, config.min_orf.is_some()),
+            )
+        })
+        .transpose()?;
+    for (i, file) in config.files.iter().enumerate() {
         let result = if file == "-" {
             //stdin
             let reader = Reader::new(std::io::stdin());
-            read_fasta_sequences(String::from("STDIN"), config.min_contig_length, reader)
+            read_fasta_sequences(String::from("STDIN"), &config, reader)
         } else {
-            let reader = Reader::from_path(&file);
+            let reader = Reader::from_path(file);
             match reader {
                 Err(e) => {
                     eprintln!("{}: {}", file, e);
                     std::process::exit(1)
                 }
-                Ok(r) => read_fasta_sequences(file.to_string(), config.min_contig_length, r),
+                Ok(r) => read_fasta_sequences(file.to_string(), &config, r),
             }
         };
+        if let (Ok(info), Some(out)) = (&result, nx_curve_out.as_mut()) {
+            write_nx_curve(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, length_histogram_out.as_mut()) {
+            write_length_histogram(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, gc_histogram_out.as_mut()) {
+            write_gc_histogram(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, dinucleotides_out.as_mut()) {
+            write_dinucleotides(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, kmer_histogram_out.as_mut()) {
+            write_kmer_histogram(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, telomere_table_out.as_mut()) {
+            write_telomere_table(out, info)?;
+        }
+        if let (Ok(info), Some(path)) = (&result, config.low_complexity_bed.as_ref()) {
+            // one BED file per assembly, contig names of different assemblies may clash
+            let path = match config.files.len() {
+                1 => path.to_string(),
+                _ => numbered_path(path, i + 1),
+            };
+            let mut out = create_table(&path, &[])?;
+            write_low_complexity_bed(&mut out, info)?;
+            out.flush()?;
+        }
+        if let (Ok(info), Some(out)) = (&result, duplicates_out.as_mut()) {
+            write_duplicates(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, id_issues_out.as_mut()) {
+            write_id_issues(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, circular_table_out.as_mut()) {
+            write_circular_table(out, info)?;
+        }
+        if let (Ok(info), Some(out)) = (&result, per_contig_out.as_mut()) {
+            write_per_contig(out, info)?;
+        }
         if config.debug {
             println!("{:#?}", result);
         } else {
@@ -75,86 +733,329 @@ pub fn run(config: Config) -> QuoddelResult<()> {
         }
     }
 
+    for out in [
+        nx_curve_out.as_mut(),
+        length_histogram_out.as_mut(),
+        gc_histogram_out.as_mut(),
+        dinucleotides_out.as_mut(),
+        kmer_histogram_out.as_mut(),
+        telomere_table_out.as_mut(),
+        duplicates_out.as_mut(),
+        id_issues_out.as_mut(),
+        circular_table_out.as_mut(),
+        per_contig_out.as_mut(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        out.flush()?;
+    }
     Ok(())
 }
 
 pub fn read_fasta_sequences<T: std::io::Read>(
     name: String,
-    min_contig_length: usize,
+    config: &Config,
     mut reader: Reader<T>,
 ) -> QuoddelResult<FastaInfo> {
-    let mut info = FastaInfo {
-        name,
-        min_contig_length_cutoff_used: min_contig_length,
-        ..Default::default()
-    };
-    let mut largest_contig_ge_cutoff: usize = 0;
-    let mut seq_lengths: Vec<usize> = Vec::new();
-    let mut atgcn_vec: Vec<NucCount> = Vec::new();
+    let broken_config = config.for_broken();
+    let mut broken = config
+        .min_gap
+        .map(|_| InfoBuilder::new(format!("{}_broken", name), &broken_config, true));
+    let mut scaffolds = InfoBuilder::new(name.clone(), config, false);
+    let mut alphabet = config.alphabet;
+    let mut proteins = ProteinBuilder::default();
+    // ids of empty sequences read before the alphabet is known
+    let mut undetected: Vec<String> = Vec::new();
 
     while let Some(result) = reader.next() {
         let record = result?;
-        let seqlen = record.seq_lines().fold(0, |l, seq| l + seq.len());
-
-        info.num_contigs_ge0 += 1;
-        info.total_length_ge0 += seqlen;
-        if seqlen >= min_contig_length {
-            let atgcn = get_atgcn_num(&record.owned_seq());
-            atgcn_vec.push(atgcn);
-            seq_lengths.push(seqlen);
-            info.num_contigs_ge_cutoff += 1;
-            info.total_length_ge_cutoff += seqlen;
+        let id = String::from_utf8_lossy(record.id_bytes());
+        let desc = record.desc_bytes().map(String::from_utf8_lossy);
+        let circular_tag = config.circular && has_circular_tag(&id, desc.as_deref());
+        let seq = record.owned_seq();
+        if alphabet == Alphabet::Auto {
+            alphabet = detect_alphabet(&seq);
+            if alphabet == Alphabet::Auto {
+                undetected.push(id.to_string());
+                continue;
+            }
+            for id in undetected.drain(..) {
+                match alphabet {
+                    Alphabet::Protein => proteins.add_sequence(&id, b""),
+                    _ => scaffolds.add_sequence(&id, b"", false),
+                }
+            }
+        }
+        if alphabet == Alphabet::Protein {
+            proteins.add_sequence(&id, &seq);
+            continue;
+        }
+        scaffolds.add_sequence(&id, &seq, circular_tag);
+        if let (Some(contigs), Some(min_gap)) = (broken.as_mut(), config.min_gap) {
+            let gaps = find_n_runs(&seq, min_gap);
+            for (i, (start, end)) in split_at_gaps(seq.len(), &gaps).into_iter().enumerate() {
+                contigs.add_sequence(&format!("{}_{}", id, i + 1), &seq[start..end], false);
+            }
+        }
+    }
 
-            if seqlen >= 1000 {
-                info.num_contigs_ge1000 += 1;
-                info.total_length_ge1000 += seqlen;
+    if alphabet == Alphabet::Protein {
+        return Ok(FastaInfo {
+            name,
+            protein: Some(proteins.finish(&config.nx_percentages)),
+            ..Default::default()
+        });
+    }
+    for id in undetected {
+        scaffolds.add_sequence(&id, b"", false);
+    }
+    let mut info = scaffolds.finish();
+    if let Some(contigs) = broken {
+        info.broken = Some(Box::new(contigs.finish()));
+    }
+    Ok(info)
+}
+
+/// Collects the per-sequence values of one assembly while streaming.
+struct InfoBuilder<'a> {
+    config: &'a Config,
+    info: FastaInfo,
+    largest_contig_ge_cutoff: usize,
+    seq_lengths: Vec<usize>,
+    /// Lengths of all sequences, including those below the length cutoff.
+    all_seq_lengths: Vec<usize>,
+    nuc_sum: NucCount,
+    /// (GC bases, counted bases, length) per contig for the GC distribution.
+    contig_gc: Vec<(usize, usize, usize)>,
+    dinuc_sum: DinucCount,
+    homopolymers: HomopolymerStats,
+    entropies: Vec<f64>,
+    entropy_counter: Option<EntropyCounter>,
+    longest_orfs: Vec<usize>,
+    gap_lengths: Vec<usize>,
+    /// Keep per-contig values for per-contig tables.
+    keep_contigs: bool,
+    kmer_counter: Option<KmerCounter>,
+    duplicates: Option<DuplicateFinder>,
+    /// Not checked for the contigs of split scaffolds, their IDs are made up.
+    ids: Option<IdChecker>,
+}
+
+impl<'a> InfoBuilder<'a> {
+    /// `broken`: the builder gets the contigs of split scaffolds, with `Config::for_broken`.
+    fn new(name: String, config: &'a Config, broken: bool) -> Self {
+        let info = FastaInfo {
+            name,
+            min_contig_length_cutoff_used: config.min_contig_length,
+            est_genome_size: config.est_genome_size,
+            length_buckets: config
+                .length_thresholds
+                .iter()
+                .map(|&t| LengthBucket::new(t))
+                .collect(),
+            num_scaffolds_with_gaps: config.min_gap.map(|_| 0),
+            num_low_complexity: config.low_complexity.then_some(0),
+            circular: config.circular.then(CircularStats::default),
+            min_homopolymer: config.min_homopolymer,
+            telomeres: config
+                .telomeres
+                .as_ref()
+                .map(|(motif, window)| TelomereStats::new(motif, *window)),
+            ..Default::default()
+        };
+        InfoBuilder {
+            config,
+            info,
+            largest_contig_ge_cutoff: 0,
+            seq_lengths: Vec::new(),
+            all_seq_lengths: Vec::new(),
+            nuc_sum: NucCount::default(),
+            contig_gc: Vec::new(),
+            dinuc_sum: DinucCount::default(),
+            homopolymers: HomopolymerStats::default(),
+            entropies: Vec::new(),
+            entropy_counter: config
+                .entropy
+                .then(|| EntropyCounter::new(config.entropy_k)),
+            longest_orfs: Vec::new(),
+            gap_lengths: Vec::new(),
+            keep_contigs: config.dinucleotides.is_some()
+                || config.telomere_table.is_some()
+                || config.low_complexity_bed.is_some()
+                || config.circular_table.is_some()
+                || config.per_contig.is_some(),
+            kmer_counter: config
+                .kmer_size
+                .map(|k| KmerCounter::new(k, config.kmer_max_distinct)),
+            duplicates: config.find_duplicates.then(DuplicateFinder::default),
+            ids: (!broken).then(IdChecker::default),
+        }
+    }
+
+    /// `circular_tag`: the header marks the sequence as circular.
+    fn add_sequence(&mut self, id: &str, seq: &[u8], circular_tag: bool) {
+        let seqlen = seq.len();
+        let info = &mut self.info;
+        self.all_seq_lengths.push(seqlen);
+        if let Some(ids) = self.ids.as_mut() {
+            ids.add(id);
+        }
+
+        // like QUAST, contigs below the length cutoff only count for the >= 0 bp rows
+        for bucket in info.length_buckets.iter_mut() {
+            if seqlen >= self.config.min_contig_length || bucket.min_length == 0 {
+                bucket.add(seqlen);
             }
-            if seqlen >= 5000 {
-                info.num_contigs_ge5000 += 1;
-                info.total_length_ge5000 += seqlen;
+        }
+        if seqlen >= self.config.min_contig_length {
+            let (atgcn, homopolymers) = get_atgcn_homopolymer_num(seq, self.config.min_homopolymer);
+            self.homopolymers = self.homopolymers + homopolymers;
+            let dinuc = if self.config.cpg || self.config.dinucleotides.is_some() {
+                get_dinuc_num(seq)
+            } else {
+                DinucCount::default()
+            };
+            self.nuc_sum = self.nuc_sum + atgcn;
+            let (gc, total) = atgcn.gc_counts(self.config.gc_options);
+            self.contig_gc.push((gc, total, seqlen));
+            self.dinuc_sum = self.dinuc_sum + dinuc;
+            if let Some(counter) = self.kmer_counter.as_mut() {
+                counter.add_sequence(seq);
+            }
+            let telomere_ends = info.telomeres.as_mut().map(|telomeres| {
+                let ends = telomere_ends(seq, telomeres.motif.as_bytes(), telomeres.window);
+                telomeres.add(ends);
+                ends
+            });
+            if let Some(duplicates) = self.duplicates.as_mut() {
+                duplicates.add(id, seq);
             }
-            if seqlen >= 10000 {
-                info.num_contigs_ge10000 += 1;
-                info.total_length_ge10000 += seqlen;
+            let entropy = self
+                .entropy_counter
+                .as_mut()
+                .map(|counter| counter.entropy(seq));
+            self.entropies.extend(entropy);
+            let longest_orf = self.config.min_orf.map(|_| longest_orf(seq));
+            self.longest_orfs.extend(longest_orf);
+            let mut circularity = Circularity::default();
+            if let Some(circular) = info.circular.as_mut() {
+                circularity = Circularity {
+                    header_tag: circular_tag,
+                    end_overlap: has_end_overlap(seq, self.config.circular_overlap),
+                };
+                circular.add(circularity, seqlen);
             }
-            if seqlen >= 25000 {
-                info.num_contigs_ge25000 += 1;
-                info.total_length_ge25000 += seqlen
+            let mut low_complexity = Vec::new();
+            if let Some(num) = info.num_low_complexity.as_mut() {
+                low_complexity =
+                    find_low_complexity(seq, self.config.dust_window, self.config.dust_threshold);
+                *num += low_complexity
+                    .iter()
+                    .map(|(start, end)| end - start)
+                    .sum::<usize>();
             }
-            if seqlen >= 50000 {
-                info.num_contigs_ge50000 += 1;
-                info.total_length_ge50000 += seqlen;
+            if self.keep_contigs {
+                info.contigs.push(ContigInfo {
+                    name: id.to_string(),
+                    length: seqlen,
+                    ge_cutoff: true,
+                    nuc_count: atgcn,
+                    gc_fraction: atgcn.gc_fraction(self.config.gc_options),
+                    dinuc_count: dinuc,
+                    telomere_ends,
+                    low_complexity,
+                    circularity,
+                    entropy,
+                    longest_orf,
+                });
             }
+            self.seq_lengths.push(seqlen);
+            info.num_contigs_ge_cutoff += 1;
+            info.total_length_ge_cutoff += seqlen;
+
             // largest
-            if seqlen > largest_contig_ge_cutoff {
-                largest_contig_ge_cutoff = seqlen;
+            if seqlen > self.largest_contig_ge_cutoff {
+                self.largest_contig_ge_cutoff = seqlen;
             }
+            let gaps = find_n_runs(seq, 1);
+            self.gap_lengths
+                .extend(gaps.iter().map(|(start, end)| end - start));
+            if let (Some(num), Some(min_gap)) =
+                (info.num_scaffolds_with_gaps.as_mut(), self.config.min_gap)
+            {
+                if gaps.iter().any(|(start, end)| end - start >= min_gap) {
+                    *num += 1;
+                }
+            }
+        } else if self.config.per_contig.is_some() {
+            let atgcn = get_atgcn_num(seq);
+            info.contigs.push(ContigInfo {
+                name: id.to_string(),
+                length: seqlen,
+                ge_cutoff: false,
+                nuc_count: atgcn,
+                gc_fraction: atgcn.gc_fraction(self.config.gc_options),
+                dinuc_count: DinucCount::default(),
+                telomere_ends: None,
+                low_complexity: Vec::new(),
+                circularity: Circularity::default(),
+                entropy: self
+                    .entropy_counter
+                    .as_mut()
+                    .map(|counter| counter.entropy(seq)),
+                longest_orf: self.config.min_orf.map(|_| longest_orf(seq)),
+            });
         }
     }
 
-    let nl_stats = calc_stats(&seq_lengths);
-    info.n50_ge_cutoff = nl_stats.n50;
-    info.l50 = nl_stats.l50;
-    info.n90_ge_cutoff = nl_stats.n90;
-    info.l90 = nl_stats.l90;
-    info.largest_contig_ge_cutoff = largest_contig_ge_cutoff;
+    fn finish(self) -> FastaInfo {
+        let config = self.config;
+        let seq_lengths = self.seq_lengths;
+        let mut info = self.info;
+        let est_genome_size = config.est_genome_size;
 
-    let nucsum: NucCount = atgcn_vec.iter().fold(
-        NucCount {
-            num_a: 0,
-            num_t: 0,
-            num_c: 0,
-            num_g: 0,
-            num_n: 0,
-        },
-        |count, &new| count + new,
-    );
+        let nl_stats = calc_stats(
+            &seq_lengths,
+            &config.nx_percentages,
+            est_genome_size,
+            config.nx_curve.is_some(),
+        );
+        info.nx_ge_cutoff = nl_stats.nx;
+        info.ngx_ge_cutoff = nl_stats.ngx;
+        info.nx_curve = nl_stats.nx_curve;
+        info.ngx_curve = nl_stats.ngx_curve;
+        info.aun_ge_cutoff = calc_aun(&seq_lengths, info.total_length_ge_cutoff);
+        info.aung_ge_cutoff = est_genome_size.map(|size| calc_aun(&seq_lengths, size));
+        info.largest_contig_ge_cutoff = self.largest_contig_ge_cutoff;
+        info.length_stats_ge_cutoff = calc_length_stats(&seq_lengths);
+        info.length_stats_all = calc_length_stats(&self.all_seq_lengths);
+        info.length_histogram = calc_length_histogram(&seq_lengths, config.length_bins.as_deref());
+        info.gaps = calc_gap_stats(&self.gap_lengths);
 
-    let gcsum = nucsum.num_g + nucsum.num_c;
-    let atsum = nucsum.num_a + nucsum.num_t;
+        let nucsum = self.nuc_sum;
 
-    info.gc_percent_ge_cutoff = gcsum as f32 / (gcsum + atsum) as f32;
-    info.num_n_per_100_kbp =
-        nucsum.num_n as f32 * 100.0 / (info.total_length_ge_cutoff as f32 / 1000.0);
-    Ok(info)
+        info.gc_percent_ge_cutoff = nucsum.gc_fraction(config.gc_options);
+        info.dinuc_count = self.dinuc_sum;
+        info.homopolymers = self.homopolymers;
+        info.orfs = config
+            .min_orf
+            .map(|min_orf| calc_orf_stats(&self.longest_orfs, min_orf));
+        info.entropy = config
+            .entropy
+            .then(|| calc_entropy_stats(&self.entropies, config.entropy_k, config.min_entropy));
+        info.kmers = self.kmer_counter.map(|counter| counter.stats());
+        info.duplicates = self.duplicates.map(DuplicateFinder::finish);
+        info.ids = self.ids.map(IdChecker::finish);
+        info.cpg_o_e = config.cpg.then(|| self.dinuc_sum.cpg_o_e(&nucsum));
+        let per_100_kbp = |num: usize| num as f64 * 100_000.0 / info.total_length_ge_cutoff as f64;
+        info.num_n_per_100_kbp = per_100_kbp(nucsum.num_n);
+        info.num_ambiguous_per_100_kbp = per_100_kbp(nucsum.num_ambiguous());
+        info.num_non_iupac = nucsum.num_non_iupac();
+        info.num_soft_masked = nucsum.num_lowercase;
+        info.num_soft_masked_intervals = nucsum.num_lowercase_runs;
+
+        info.gc_distribution = calc_gc_distribution(&self.contig_gc);
+        info
+    }
 }
@@ -1,5 +1,6 @@
 fn main() {
     if let Err(e) = quoddel::get_args().and_then(quoddel::run) {
         eprintln!("{}", e);
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,93 @@
+use crate::calc::{calc_length_stats, reverse_complement, LengthStats};
+
+fn is_start(codon: &[u8]) -> bool {
+    codon.eq_ignore_ascii_case(b"ATG")
+}
+
+/// Stop codons of the standard genetic code.
+fn is_stop(codon: &[u8]) -> bool {
+    [b"TAA", b"TAG", b"TGA"]
+        .iter()
+        .any(|stop| codon.eq_ignore_ascii_case(*stop))
+}
+
+/// Longest ORF (ATG to stop, in amino acids without the stop) in the three forward frames.
+/// ORFs running off the end of the sequence are counted up to the end.
+fn longest_forward_orf(seq: &[u8]) -> usize {
+    let mut longest = 0;
+    for frame in 0..3 {
+        let codons = seq.get(frame..).unwrap_or_default().chunks_exact(3);
+        let num_codons = codons.len();
+        let mut start: Option<usize> = None;
+        for (i, codon) in codons.enumerate() {
+            if is_stop(codon) {
+                if let Some(s) = start.take() {
+                    longest = longest.max(i - s);
+                }
+            } else if start.is_none() && is_start(codon) {
+                start = Some(i);
+            }
+        }
+        if let Some(s) = start {
+            longest = longest.max(num_codons - s);
+        }
+    }
+    longest
+}
+
+/// Longest ORF in amino acids over all six frames, case-insensitive.
+pub fn longest_orf(seq: &[u8]) -> usize {
+    longest_forward_orf(seq).max(longest_forward_orf(&reverse_complement(seq)))
+}
+
+/// Distribution of the longest ORF per contig.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OrfStats {
+    pub min_length: usize,
+    /// Longest ORF lengths (aa) of the contigs.
+    pub lengths: LengthStats,
+    pub num_contigs: usize,
+    /// Contigs with an ORF of at least `min_length` aa.
+    pub num_contigs_ge_min: usize,
+}
+
+pub fn calc_orf_stats(longest_orfs: &[usize], min_length: usize) -> OrfStats {
+    OrfStats {
+        min_length,
+        lengths: calc_length_stats(longest_orfs),
+        num_contigs: longest_orfs.len(),
+        num_contigs_ge_min: longest_orfs.iter().filter(|&&l| l >= min_length).count(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calc::reverse_complement;
+    use crate::orf::{calc_orf_stats, longest_orf};
+
+    #[test]
+    fn test_longest_orf() {
+        assert_eq!(longest_orf(b""), 0);
+        assert_eq!(longest_orf(b"CCCCCCCCC"), 0);
+        // ATG AAA CCC TAA
+        assert_eq!(longest_orf(b"ATGAAACCCTAA"), 3);
+        // frame 2, lowercase
+        assert_eq!(longest_orf(b"ccatgaaataggg"), 2);
+        // inner ATG does not restart the ORF
+        assert_eq!(longest_orf(b"ATGATGAAATGA"), 3);
+        // open at the end
+        assert_eq!(longest_orf(b"GGATGAAACCC"), 3);
+        // reverse strand
+        let seq = reverse_complement(b"ATGAAACCCGGGTTTTGA");
+        assert_eq!(longest_orf(&seq), 5);
+    }
+
+    #[test]
+    fn test_orf_stats() {
+        let stats = calc_orf_stats(&[150, 20, 100, 0], 100);
+        assert_eq!(stats.num_contigs, 4);
+        assert_eq!(stats.num_contigs_ge_min, 2);
+        assert_eq!(stats.lengths.largest, 150);
+        assert_eq!(stats.lengths.median, 60.0);
+    }
+}
@@ -1,87 +1,733 @@
+use crate::calc::{
+    DinucCount, EntropyStats, GapStats, GcDistribution, HomopolymerStats, LengthBin, LengthBucket,
+    LengthStats, NucCount, NxStat, DINUCLEOTIDES,
+};
+use crate::circular::{CircularStats, Circularity};
+use crate::duplicates::DuplicateStats;
+use crate::ids::IdStats;
+use crate::kmer::KmerStats;
+use crate::orf::OrfStats;
+use crate::protein::{ProteinStats, AMINO_ACIDS};
+use crate::telomere::TelomereStats;
+use std::collections::HashMap;
 use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 
 #[derive(Default, Debug)]
 pub struct FastaInfo {
     pub(crate) name: String,
     pub(crate) min_contig_length_cutoff_used: usize,
-    pub(crate) num_contigs_ge0: usize,
-    pub(crate) num_contigs_ge1000: usize,
-    pub(crate) num_contigs_ge5000: usize,
-    pub(crate) num_contigs_ge10000: usize,
-    pub(crate) num_contigs_ge25000: usize,
-    pub(crate) num_contigs_ge50000: usize,
-
-    pub(crate) total_length_ge0: usize,
-    pub(crate) total_length_ge1000: usize,
-    pub(crate) total_length_ge5000: usize,
-    pub(crate) total_length_ge10000: usize,
-    pub(crate) total_length_ge25000: usize,
-    pub(crate) total_length_ge50000: usize,
+    pub(crate) est_genome_size: Option<usize>,
+    pub(crate) length_buckets: Vec<LengthBucket>,
+    pub(crate) length_histogram: Vec<LengthBin>,
 
     pub(crate) num_contigs_ge_cutoff: usize,
 
     pub(crate) largest_contig_ge_cutoff: usize,
-    pub(crate) gc_percent_ge_cutoff: f32,
-    pub(crate) n50_ge_cutoff: usize,
-    pub(crate) n90_ge_cutoff: usize,
-    //auN: f32, ?
-    pub(crate) l50: usize,
-    pub(crate) l90: usize,
-    pub(crate) num_n_per_100_kbp: f32,
+    pub(crate) length_stats_ge_cutoff: LengthStats,
+    /// Lengths of all sequences, ignoring the length cutoff.
+    pub(crate) length_stats_all: LengthStats,
+    pub(crate) gc_percent_ge_cutoff: f64,
+    pub(crate) gc_distribution: GcDistribution,
+    pub(crate) nx_ge_cutoff: Vec<NxStat>,
+    pub(crate) ngx_ge_cutoff: Vec<Option<NxStat>>,
+    pub(crate) nx_curve: Vec<NxStat>,
+    pub(crate) ngx_curve: Vec<Option<NxStat>>,
+    pub(crate) aun_ge_cutoff: f64,
+    pub(crate) aung_ge_cutoff: Option<f64>,
+    pub(crate) num_n_per_100_kbp: f64,
+    pub(crate) num_ambiguous_per_100_kbp: f64,
+    /// X, '-' and anything else that is not a base or IUPAC code.
+    pub(crate) num_non_iupac: usize,
+    /// Lowercase bases.
+    pub(crate) num_soft_masked: usize,
+    pub(crate) num_soft_masked_intervals: usize,
     pub(crate) total_length_ge_cutoff: usize,
+    pub(crate) gaps: GapStats,
+    /// Only set when splitting scaffolds.
+    pub(crate) num_scaffolds_with_gaps: Option<usize>,
+    pub(crate) dinuc_count: DinucCount,
+    /// Only computed if requested, like the other optional analyses below.
+    pub(crate) cpg_o_e: Option<f64>,
+    pub(crate) kmers: Option<KmerStats>,
+    pub(crate) min_homopolymer: usize,
+    pub(crate) homopolymers: HomopolymerStats,
+    pub(crate) entropy: Option<EntropyStats>,
+    pub(crate) orfs: Option<OrfStats>,
+    pub(crate) telomeres: Option<TelomereStats>,
+    /// Bases in DUST low-complexity intervals.
+    pub(crate) num_low_complexity: Option<usize>,
+    pub(crate) duplicates: Option<DuplicateStats>,
+    pub(crate) circular: Option<CircularStats>,
+    /// Checks of the IDs of all sequences, regardless of length; not for split scaffolds.
+    pub(crate) ids: Option<IdStats>,
+    /// Only collected if a per-contig table is requested.
+    pub(crate) contigs: Vec<ContigInfo>,
+    /// Set instead of the nucleotide stats for protein fasta files.
+    pub(crate) protein: Option<ProteinStats>,
+    /// Contig-level stats when splitting scaffolds at gaps.
+    pub(crate) broken: Option<Box<FastaInfo>>,
 }
 
-impl fmt::Display for FastaInfo {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+/// Values of a single sequence. Sequences below the length cutoff are only kept for
+/// the per-contig table, with their composition, entropy and longest ORF.
+#[derive(Debug, Clone)]
+pub struct ContigInfo {
+    pub(crate) name: String,
+    pub(crate) length: usize,
+    /// At least as long as the length cutoff.
+    pub(crate) ge_cutoff: bool,
+    pub(crate) nuc_count: NucCount,
+    pub(crate) gc_fraction: f64,
+    pub(crate) dinuc_count: DinucCount,
+    /// Telomere repeat lengths at the start and end.
+    pub(crate) telomere_ends: Option<(usize, usize)>,
+    /// Low-complexity intervals (start, end exclusive).
+    pub(crate) low_complexity: Vec<(usize, usize)>,
+    pub(crate) circularity: Circularity,
+    /// Shannon entropy of the bases or k-mers, only computed if requested.
+    pub(crate) entropy: Option<f64>,
+    /// Longest ORF in amino acids, only searched if requested.
+    pub(crate) longest_orf: Option<usize>,
+}
+
+/// QUAST prints "-" for metrics that can't be calculated, e.g. NG50 for too small assemblies.
+fn or_dash(value: Option<usize>) -> String {
+    match value {
+        Some(v) => v.to_string(),
+        None => String::from("-"),
+    }
+}
+
+/// Creates an additional tab-separated output file and writes its header line, if any.
+pub(crate) fn create_table(path: &str, header: &[&str]) -> io::Result<BufWriter<File>> {
+    let mut out = BufWriter::new(File::create(path)?);
+    if !header.is_empty() {
+        writeln!(out, "{}", header.join("\t"))?;
+    }
+    Ok(out)
+}
+
+/// Path of the output file of one of several assemblies: the (1-based) number
+/// of the assembly goes before the extension, e.g. "lc.bed" -> "lc.2.bed".
+pub(crate) fn numbered_path(path: &str, number: usize) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, number, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, number),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+pub(crate) fn nx_curve_header(with_ref: bool) -> Vec<&'static str> {
+    let mut header = vec!["assembly", "x", "Nx", "Lx"];
+    if with_ref {
+        header.extend(["NGx", "LGx"]);
+    }
+    header
+}
+
+/// Writes the Nx curve (x = 0..100) in long format, one row per x,
+/// followed by the curve of the broken contigs if present.
+pub(crate) fn write_nx_curve<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for (i, nx) in info.nx_curve.iter().enumerate() {
+        write!(out, "{}\t{}\t{}\t{}", info.name, nx.percent, nx.n, nx.l)?;
+        if info.est_genome_size.is_some() {
+            let ngx = info.ngx_curve[i];
+            write!(
+                out,
+                "\t{}\t{}",
+                or_dash(ngx.map(|s| s.n)),
+                or_dash(ngx.map(|s| s.l))
+            )?;
+        }
+        writeln!(out)?;
+    }
+    if let Some(broken) = &info.broken {
+        write_nx_curve(out, broken)?;
+    }
+    Ok(())
+}
+
+pub(crate) const LENGTH_HISTOGRAM_HEADER: &[&str] = &[
+    "assembly",
+    "min length",
+    "max length",
+    "num contigs",
+    "total length",
+];
+
+/// Writes the contig length histogram, one row per bin. The last bin
+/// of user-defined bins has no upper bound and shows "-" as max length.
+pub(crate) fn write_length_histogram<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for bin in &info.length_histogram {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}",
+            info.name,
+            bin.min_length,
+            or_dash(bin.max_length.map(|max| max - 1)),
+            bin.num_contigs,
+            bin.total_length
+        )?;
+    }
+    if let Some(broken) = &info.broken {
+        write_length_histogram(out, broken)?;
+    }
+    Ok(())
+}
+
+pub(crate) const GC_HISTOGRAM_HEADER: &[&str] =
+    &["assembly", "GC (%)", "num contigs", "total length"];
+
+/// Writes the per-contig GC histogram, one row per 1% bin (lower bound).
+pub(crate) fn write_gc_histogram<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for (percent, bin) in info.gc_distribution.histogram.iter().enumerate() {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}",
+            info.name, percent, bin.num_contigs, bin.total_length
+        )?;
+    }
+    if let Some(broken) = &info.broken {
+        write_gc_histogram(out, broken)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn dinucleotides_header() -> Vec<&'static str> {
+    let mut header = vec!["assembly", "contig", "length"];
+    header.extend(DINUCLEOTIDES);
+    header.push("CpG o/e");
+    header
+}
+
+/// Writes the dinucleotide counts and CpG o/e of each contig.
+pub(crate) fn write_dinucleotides<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for contig in info.contigs_ge_cutoff() {
+        write!(out, "{}\t{}\t{}", info.name, contig.name, contig.length)?;
+        for count in contig.dinuc_count.counts {
+            write!(out, "\t{}", count)?;
+        }
+        writeln!(
+            out,
+            "\t{:.3}",
+            contig.dinuc_count.cpg_o_e(&contig.nuc_count)
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) const KMER_HISTOGRAM_HEADER: &[&str] =
+    &["assembly", "k", "multiplicity", "num distinct k-mers"];
+
+/// Writes the k-mer multiplicity histogram, one row per observed multiplicity.
+pub(crate) fn write_kmer_histogram<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    if let Some(kmers) = &info.kmers {
+        for (multiplicity, count) in &kmers.histogram {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}",
+                info.name, kmers.k, multiplicity, count
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) const TELOMERE_TABLE_HEADER: &[&str] = &[
+    "assembly",
+    "contig",
+    "length",
+    "start telomere length",
+    "end telomere length",
+];
+
+/// Writes the telomere repeat lengths at both ends of each contig.
+pub(crate) fn write_telomere_table<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for contig in info.contigs_ge_cutoff() {
+        if let Some((start, end)) = contig.telomere_ends {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}\t{}",
+                info.name, contig.name, contig.length, start, end
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the low-complexity intervals of each contig in BED format (0-based, end exclusive).
+pub(crate) fn write_low_complexity_bed<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for contig in info.contigs_ge_cutoff() {
+        for (start, end) in &contig.low_complexity {
+            writeln!(out, "{}\t{}\t{}", contig.name, start, end)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) const DUPLICATES_HEADER: &[&str] =
+    &["assembly", "group", "contig", "length", "orientation"];
+
+/// Writes the duplicate groups, one row per contig. Orientation is "-"
+/// for reverse complements of the first contig of the group.
+pub(crate) fn write_duplicates<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    let groups = info
+        .duplicates
+        .iter()
+        .flat_map(|duplicates| &duplicates.groups);
+    for (i, group) in groups.enumerate() {
+        for contig in group {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}\t{}",
+                info.name,
+                i + 1,
+                contig.name,
+                contig.length,
+                if contig.reverse { "-" } else { "+" }
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) const ID_ISSUES_HEADER: &[&str] = &["assembly", "id", "issue"];
+
+/// Writes one row per problem with a sequence ID.
+pub(crate) fn write_id_issues<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    let ids = match &info.protein {
+        Some(protein) => Some(&protein.ids),
+        None => info.ids.as_ref(),
+    };
+    for (id, issue) in ids.iter().flat_map(|ids| &ids.issues) {
+        writeln!(out, "{}\t{}\t{}", info.name, id, issue)?;
+    }
+    Ok(())
+}
+
+pub(crate) const CIRCULAR_TABLE_HEADER: &[&str] = &[
+    "assembly",
+    "contig",
+    "length",
+    "circular",
+    "header tag",
+    "end overlap",
+];
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Writes one row per contig with the evidence for it being circular.
+pub(crate) fn write_circular_table<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    for contig in info.contigs_ge_cutoff() {
+        let circularity = contig.circularity;
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            info.name,
+            contig.name,
+            contig.length,
+            yes_no(circularity.is_circular()),
+            yes_no(circularity.header_tag),
+            yes_no(circularity.end_overlap)
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn per_contig_header(with_entropy: bool, with_orfs: bool) -> Vec<&'static str> {
+    let mut header = vec![
+        "assembly",
+        "contig",
+        "length",
+        "length >= cutoff",
+        "GC (%)",
+        "num N's",
+        "num ambiguous bases",
+        "soft-masked bases",
+    ];
+    if with_entropy {
+        header.extend(["entropy", "low entropy"]);
+    }
+    if with_orfs {
+        header.push("longest ORF (aa)");
+    }
+    header
+}
+
+/// Writes length and composition of each sequence, including those below the length cutoff, longest first.
+pub(crate) fn write_per_contig<W: Write>(out: &mut W, info: &FastaInfo) -> io::Result<()> {
+    let mut contigs: Vec<&ContigInfo> = info.contigs.iter().collect();
+    contigs.sort_by_key(|contig| std::cmp::Reverse(contig.length));
+    for contig in contigs {
         write!(
-            f,
-            "Assembly\t{}
-num contigs (>= 0 bp)\t{}
-num contigs (>= 1000 bp)\t{}
-num contigs (>= 5000 bp)\t{}
-num contigs (>= 10000 bp)\t{}
-num contigs (>= 25000 bp)\t{}
-num contigs (>= 50000 bp)\t{}
-total length (>= 0 bp)\t{}
-total length (>= 1000 bp)\t{}
-total length (>= 5000 bp)\t{}
-total length (>= 10000 bp)\t{}
-total length (>= 25000 bp)\t{}
-total length (>= 50000 bp)\t{}
-minimum contig length cutoff\t{}
-num contigs\t{}
-largest contig\t{}
-total length\t{}
-GC (%)\t{:.3}
-N50\t{}
-N90\t{}
-L50\t{}
-L90\t{}
-num N's per 100 kbp\t{:.3}
-        ",
-            self.name,
-            self.num_contigs_ge0,
-            self.num_contigs_ge1000,
-            self.num_contigs_ge5000,
-            self.num_contigs_ge10000,
-            self.num_contigs_ge25000,
-            self.num_contigs_ge50000,
-            self.total_length_ge0,
-            self.total_length_ge1000,
-            self.total_length_ge5000,
-            self.total_length_ge10000,
-            self.total_length_ge25000,
-            self.total_length_ge50000,
+            out,
+            "{}\t{}\t{}\t{}\t{:.3}\t{}\t{}\t{}",
+            info.name,
+            contig.name,
+            contig.length,
+            yes_no(contig.ge_cutoff),
+            contig.gc_fraction * 100.0,
+            contig.nuc_count.num_n,
+            contig.nuc_count.num_ambiguous(),
+            contig.nuc_count.num_lowercase
+        )?;
+        if let (Some(entropy), Some(stats)) = (contig.entropy, &info.entropy) {
+            write!(
+                out,
+                "\t{:.3}\t{}",
+                entropy,
+                yes_no(entropy < stats.min_entropy)
+            )?;
+        }
+        if let Some(longest_orf) = contig.longest_orf {
+            write!(out, "\t{}", longest_orf)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+impl FastaInfo {
+    /// Area under the Nx curve (contigs above the length cutoff).
+    pub fn aun(&self) -> f64 {
+        self.aun_ge_cutoff
+    }
+
+    /// Area under the NGx curve, only available with an estimated genome size.
+    pub fn aung(&self) -> Option<f64> {
+        self.aung_ge_cutoff
+    }
+
+    fn contigs_ge_cutoff(&self) -> impl Iterator<Item = &ContigInfo> {
+        self.contigs.iter().filter(|contig| contig.ge_cutoff)
+    }
+
+    /// Report rows as (metric, value), in QUAST order.
+    fn rows(&self) -> Vec<(String, String)> {
+        if let Some(protein) = &self.protein {
+            return protein_rows(&self.name, protein);
+        }
+        let with_ref = self.est_genome_size.is_some();
+        let mut rows = vec![row("Assembly", &self.name)];
+        for bucket in &self.length_buckets {
+            rows.push(row(
+                format!("num contigs (>= {} bp)", bucket.min_length),
+                bucket.num_contigs,
+            ));
+        }
+        for bucket in &self.length_buckets {
+            rows.push(row(
+                format!("total length (>= {} bp)", bucket.min_length),
+                bucket.total_length,
+            ));
+        }
+        rows.push(row(
+            "minimum contig length cutoff",
             self.min_contig_length_cutoff_used,
-            self.num_contigs_ge_cutoff,
-            self.largest_contig_ge_cutoff,
-            self.total_length_ge_cutoff,
-            self.gc_percent_ge_cutoff * 100.0,
-            self.n50_ge_cutoff,
-            self.n90_ge_cutoff,
-            self.l50,
-            self.l90,
-            self.num_n_per_100_kbp,
-        )
+        ));
+        rows.push(row("num contigs", self.num_contigs_ge_cutoff));
+        rows.push(row("largest contig", self.largest_contig_ge_cutoff));
+        rows.push(row("total length", self.total_length_ge_cutoff));
+        if let Some(size) = self.est_genome_size {
+            rows.push(row("estimated reference length", size));
+        }
+        rows.push(row(
+            "GC (%)",
+            format!("{:.3}", self.gc_percent_ge_cutoff * 100.0),
+        ));
+        for (i, nx) in self.nx_ge_cutoff.iter().enumerate() {
+            rows.push(row(format!("N{}", nx.percent), nx.n));
+            if with_ref {
+                let ngx = self.ngx_ge_cutoff[i].map(|s| s.n);
+                rows.push(row(format!("NG{}", nx.percent), or_dash(ngx)));
+            }
+        }
+        rows.push(row("auN", format!("{:.1}", self.aun_ge_cutoff)));
+        if let Some(aung) = self.aung_ge_cutoff {
+            rows.push(row("auNG", format!("{:.1}", aung)));
+        }
+        for (i, nx) in self.nx_ge_cutoff.iter().enumerate() {
+            rows.push(row(format!("L{}", nx.percent), nx.l));
+            if with_ref {
+                let lgx = self.ngx_ge_cutoff[i].map(|s| s.l);
+                rows.push(row(format!("LG{}", nx.percent), or_dash(lgx)));
+            }
+        }
+        rows.push(row(
+            "num N's per 100 kbp",
+            format!("{:.3}", self.num_n_per_100_kbp),
+        ));
+        // not in QUAST's report
+        if let Some(num) = self.num_scaffolds_with_gaps {
+            rows.push(row("num scaffolds with gaps", num));
+        }
+        let lengths = &self.length_stats_ge_cutoff;
+        rows.push(row("smallest contig", lengths.smallest));
+        rows.push(row("mean contig length", format!("{:.1}", lengths.mean)));
+        rows.push(row(
+            "median contig length",
+            format!("{:.1}", lengths.median),
+        ));
+        rows.push(row("contig length std dev", format!("{:.1}", lengths.sd)));
+        let lengths = &self.length_stats_all;
+        rows.push(row("largest contig (>= 0 bp)", lengths.largest));
+        rows.push(row("smallest contig (>= 0 bp)", lengths.smallest));
+        rows.push(row(
+            "mean contig length (>= 0 bp)",
+            format!("{:.1}", lengths.mean),
+        ));
+        rows.push(row(
+            "median contig length (>= 0 bp)",
+            format!("{:.1}", lengths.median),
+        ));
+        rows.push(row(
+            "contig length std dev (>= 0 bp)",
+            format!("{:.1}", lengths.sd),
+        ));
+        let gc = &self.gc_distribution;
+        rows.push(row("mean contig GC (%)", format!("{:.3}", gc.mean)));
+        rows.push(row("contig GC (%) std dev", format!("{:.3}", gc.sd)));
+        rows.push(row("min contig GC (%)", format!("{:.3}", gc.min)));
+        rows.push(row("max contig GC (%)", format!("{:.3}", gc.max)));
+        if let Some(cpg_o_e) = self.cpg_o_e {
+            rows.push(row("CpG o/e", format!("{:.3}", cpg_o_e)));
+        }
+        rows.push(row(
+            "num ambiguous bases per 100 kbp",
+            format!("{:.3}", self.num_ambiguous_per_100_kbp),
+        ));
+        rows.push(row("num other characters", self.num_non_iupac));
+        rows.push(row("soft-masked bases", self.num_soft_masked));
+        rows.push(row(
+            "soft-masked (%)",
+            format!(
+                "{:.3}",
+                self.num_soft_masked as f64 * 100.0 / self.total_length_ge_cutoff as f64
+            ),
+        ));
+        rows.push(row(
+            "num soft-masked intervals",
+            self.num_soft_masked_intervals,
+        ));
+        if let Some(kmers) = &self.kmers {
+            rows.push(row(format!("num {}-mers", kmers.k), kmers.num_kmers));
+            rows.push(row(
+                format!("num distinct {}-mers", kmers.k),
+                kmers.num_distinct,
+            ));
+            rows.push(row(
+                format!("distinct {}-mers occurring more than once (%)", kmers.k),
+                format!(
+                    "{:.3}",
+                    kmers.num_repeated as f64 * 100.0 / kmers.num_distinct as f64
+                ),
+            ));
+            rows.push(row(
+                format!("{}-mer counting capped", kmers.k),
+                yes_no(kmers.capped),
+            ));
+        }
+        for (i, base) in ["A", "C", "G", "T"].iter().enumerate() {
+            rows.push(row(
+                format!("num {} homopolymers (>= {} bp)", base, self.min_homopolymer),
+                self.homopolymers.num_runs[i],
+            ));
+        }
+        for (i, base) in ["A", "C", "G", "T"].iter().enumerate() {
+            rows.push(row(
+                format!("longest {} homopolymer", base),
+                self.homopolymers.longest[i],
+            ));
+        }
+        rows.push(row(
+            format!("bases in homopolymers (>= {} bp) (%)", self.min_homopolymer),
+            format!(
+                "{:.3}",
+                self.homopolymers.num_bases_in_runs as f64 * 100.0
+                    / self.total_length_ge_cutoff as f64
+            ),
+        ));
+        if let Some(num) = self.num_low_complexity {
+            rows.push(row("low-complexity bases", num));
+            rows.push(row(
+                "low-complexity (%)",
+                format!(
+                    "{:.3}",
+                    num as f64 * 100.0 / self.total_length_ge_cutoff as f64
+                ),
+            ));
+        }
+        if let Some(duplicates) = &self.duplicates {
+            rows.push(row("num duplicate groups", duplicates.num_groups));
+            rows.push(row("num duplicate contigs", duplicates.num_duplicates));
+            rows.push(row("duplicated length", duplicates.duplicated_length));
+        }
+        if let Some(circular) = &self.circular {
+            rows.push(row("num circular contigs", circular.num_contigs));
+            rows.push(row(
+                "total length of circular contigs",
+                circular.total_length,
+            ));
+        }
+        if let Some(entropy) = &self.entropy {
+            let entropy_label = match entropy.k {
+                1 => String::from("entropy"),
+                k => format!("{}-mer entropy", k),
+            };
+            rows.push(row(
+                format!("mean contig {}", entropy_label),
+                format!("{:.3}", entropy.mean),
+            ));
+            rows.push(row(
+                format!(
+                    "num contigs with {} < {}",
+                    entropy_label, entropy.min_entropy
+                ),
+                entropy.num_low_entropy,
+            ));
+        }
+        if let Some(orfs) = &self.orfs {
+            rows.push(row("largest ORF (aa)", orfs.lengths.largest));
+            rows.push(row(
+                "mean longest ORF (aa)",
+                format!("{:.1}", orfs.lengths.mean),
+            ));
+            rows.push(row(
+                "median longest ORF (aa)",
+                format!("{:.1}", orfs.lengths.median),
+            ));
+            rows.push(row(
+                "longest ORF std dev",
+                format!("{:.1}", orfs.lengths.sd),
+            ));
+            rows.push(row(
+                format!("contigs with ORF >= {} aa (%)", orfs.min_length),
+                format!(
+                    "{:.3}",
+                    orfs.num_contigs_ge_min as f64 * 100.0 / orfs.num_contigs as f64
+                ),
+            ));
+        }
+        if let Some(telomeres) = &self.telomeres {
+            rows.push(row(
+                "num contigs with telomere at one end",
+                telomeres.num_contigs_one_end,
+            ));
+            rows.push(row(
+                "num contigs with telomere at both ends",
+                telomeres.num_contigs_both_ends,
+            ));
+        }
+        rows.push(row("num gaps", self.gaps.num_gaps));
+        rows.push(row("total gap length", self.gaps.total_gap_length));
+        rows.push(row("largest gap", self.gaps.largest_gap));
+        rows.push(row(
+            "mean gap length",
+            format!("{:.1}", self.gaps.mean_gap_length),
+        ));
+        rows.push(row(
+            "median gap length",
+            format!("{:.1}", self.gaps.median_gap_length),
+        ));
+        rows.push(row("num gaps of 100 Ns", self.gaps.num_gaps_100n));
+        if let Some(ids) = &self.ids {
+            rows.extend(id_rows(ids));
+        }
+        rows
+    }
+}
+
+/// Report rows of a protein fasta file, over all sequences.
+fn protein_rows(name: &str, stats: &ProteinStats) -> Vec<(String, String)> {
+    let percent = |num: usize| format!("{:.3}", num as f64 * 100.0 / stats.total_length as f64);
+    let mut rows = vec![row("Assembly", name)];
+    rows.push(row("num sequences", stats.num_sequences));
+    rows.push(row("total length", stats.total_length));
+    rows.push(row("largest sequence", stats.lengths.largest));
+    rows.push(row("smallest sequence", stats.lengths.smallest));
+    rows.push(row(
+        "mean sequence length",
+        format!("{:.1}", stats.lengths.mean),
+    ));
+    rows.push(row(
+        "median sequence length",
+        format!("{:.1}", stats.lengths.median),
+    ));
+    rows.push(row(
+        "sequence length std dev",
+        format!("{:.1}", stats.lengths.sd),
+    ));
+    for nx in &stats.nx {
+        rows.push(row(format!("N{}", nx.percent), nx.n));
+    }
+    for nx in &stats.nx {
+        rows.push(row(format!("L{}", nx.percent), nx.l));
+    }
+    for &aa in AMINO_ACIDS {
+        rows.push(row(
+            format!("{} (%)", aa as char),
+            percent(stats.aa_count.get(aa)),
+        ));
+    }
+    rows.push(row("X (%)", percent(stats.aa_count.get(b'X'))));
+    rows.push(row(
+        "num non-standard amino acids",
+        stats.aa_count.num_non_standard(),
+    ));
+    rows.push(row("num other characters", stats.aa_count.num_other));
+    rows.push(row("num internal stops", stats.num_internal_stops));
+    rows.push(row(
+        "num sequences with internal stops",
+        stats.num_sequences_internal_stops,
+    ));
+    rows.push(row(
+        "num sequences not starting with M",
+        stats.num_no_start_m,
+    ));
+    rows.extend(id_rows(&stats.ids));
+    rows
+}
+
+fn id_rows(ids: &IdStats) -> Vec<(String, String)> {
+    vec![
+        row("num duplicate sequence IDs", ids.num_duplicate),
+        row("num empty sequence IDs", ids.num_empty),
+        row(
+            "num sequence IDs with problematic characters",
+            ids.num_problematic,
+        ),
+    ]
+}
+
+fn row(label: impl Into<String>, value: impl ToString) -> (String, String) {
+    (label.into(), value.to_string())
+}
+
+impl fmt::Display for FastaInfo {
+    /// One metric per line; with split scaffolds, the contig-level
+    /// values follow in a second column like QUAST's "_broken" assemblies.
+    /// Rows missing for the contigs, such as the k-mer spectrum, show "-".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let broken_rows: Option<HashMap<String, String>> =
+            self.broken.as_ref().map(|b| b.rows().into_iter().collect());
+        for (label, value) in self.rows() {
+            write!(f, "{}\t{}", label, value)?;
+            if let Some(broken) = &broken_rows {
+                write!(f, "\t{}", broken.get(&label).map_or("-", String::as_str))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
     }
 }
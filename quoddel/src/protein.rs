@@ -0,0 +1,189 @@
+use crate::calc::{calc_length_stats, calc_stats, LengthStats, NxStat};
+use crate::ids::{IdChecker, IdStats};
+
+/// The 20 standard amino acids in one-letter code.
+pub const AMINO_ACIDS: &[u8; 20] = b"ACDEFGHIKLMNPQRSTVWY";
+
+/// Amino acid codes (and stop) that are not nucleotide IUPAC codes.
+const PROTEIN_ONLY: &[u8] = b"EFIJLOPQZ*";
+
+/// A sequence is taken as protein if more than this fraction of its
+/// residues are `PROTEIN_ONLY` codes, about a third in typical proteins.
+const MIN_PROTEIN_ONLY_FRACTION: f64 = 0.1;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Alphabet {
+    Auto,
+    Nucleotide,
+    Protein,
+}
+
+/// Guesses the alphabet from the composition of one sequence.
+pub fn detect_alphabet(seq: &[u8]) -> Alphabet {
+    let residues = seq
+        .iter()
+        .filter(|c| c.is_ascii_alphabetic() || **c == b'*')
+        .count();
+    let protein_only = seq
+        .iter()
+        .filter(|c| PROTEIN_ONLY.contains(&c.to_ascii_uppercase()))
+        .count();
+    if residues == 0 {
+        Alphabet::Auto
+    } else if protein_only as f64 > residues as f64 * MIN_PROTEIN_ONLY_FRACTION {
+        Alphabet::Protein
+    } else {
+        Alphabet::Nucleotide
+    }
+}
+
+/// Residue counts of protein sequences, case-insensitive.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct AaCount {
+    /// Counts per letter A..Z, including X and non-standard codes (B, J, O, U, Z).
+    pub letters: [usize; 26],
+    /// '*' anywhere but at the end of a sequence.
+    pub num_internal_stops: usize,
+    /// Gaps ('-', '.') and anything else.
+    pub num_other: usize,
+}
+
+impl AaCount {
+    /// Adds a sequence, a single trailing stop is not counted.
+    pub fn add_sequence(&mut self, seq: &[u8]) {
+        for &c in strip_stop(seq) {
+            match c {
+                b'A'..=b'Z' => self.letters[(c - b'A') as usize] += 1,
+                b'a'..=b'z' => self.letters[(c - b'a') as usize] += 1,
+                b'*' => self.num_internal_stops += 1,
+                _ => self.num_other += 1,
+            }
+        }
+    }
+
+    pub fn get(&self, aa: u8) -> usize {
+        self.letters[(aa.to_ascii_uppercase() - b'A') as usize]
+    }
+
+    /// Letters that are neither a standard amino acid nor X.
+    pub fn num_non_standard(&self) -> usize {
+        (b'A'..=b'Z')
+            .filter(|aa| !AMINO_ACIDS.contains(aa) && *aa != b'X')
+            .map(|aa| self.get(aa))
+            .sum()
+    }
+}
+
+/// The sequence without a trailing stop ('*').
+fn strip_stop(seq: &[u8]) -> &[u8] {
+    seq.strip_suffix(b"*").unwrap_or(seq)
+}
+
+/// Stats of a protein fasta file, over all sequences.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProteinStats {
+    pub num_sequences: usize,
+    /// Total number of residues, without trailing stops.
+    pub total_length: usize,
+    pub lengths: LengthStats,
+    pub nx: Vec<NxStat>,
+    pub aa_count: AaCount,
+    pub num_internal_stops: usize,
+    /// Sequences with internal stops.
+    pub num_sequences_internal_stops: usize,
+    pub num_no_start_m: usize,
+    pub ids: IdStats,
+}
+
+/// Collects protein stats while streaming.
+#[derive(Debug, Default)]
+pub struct ProteinBuilder {
+    seq_lengths: Vec<usize>,
+    stats: ProteinStats,
+    ids: IdChecker,
+}
+
+impl ProteinBuilder {
+    pub fn add_sequence(&mut self, id: &str, seq: &[u8]) {
+        self.ids.add(id);
+        let stats = &mut self.stats;
+        let stops_before = stats.aa_count.num_internal_stops;
+        stats.aa_count.add_sequence(seq);
+        let stops = stats.aa_count.num_internal_stops - stops_before;
+        let seqlen = strip_stop(seq).len();
+        stats.num_sequences += 1;
+        stats.total_length += seqlen;
+        stats.num_internal_stops += stops;
+        if stops > 0 {
+            stats.num_sequences_internal_stops += 1;
+        }
+        if !matches!(seq.first(), Some(b'M' | b'm')) {
+            stats.num_no_start_m += 1;
+        }
+        self.seq_lengths.push(seqlen);
+    }
+
+    pub fn finish(self, nx_percentages: &[usize]) -> ProteinStats {
+        let mut stats = self.stats;
+        stats.lengths = calc_length_stats(&self.seq_lengths);
+        stats.nx = calc_stats(&self.seq_lengths, nx_percentages, None, false).nx;
+        stats.ids = self.ids.finish();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calc::NxStat;
+    use crate::protein::{detect_alphabet, AaCount, Alphabet, ProteinBuilder};
+
+    #[test]
+    fn test_detect_alphabet() {
+        assert_eq!(detect_alphabet(b"ACGTNNacgtRY"), Alphabet::Nucleotide);
+        assert_eq!(detect_alphabet(b"RYSWKMBDHVNN"), Alphabet::Nucleotide);
+        assert_eq!(detect_alphabet(b"MKVLAAGIVGLLLA*"), Alphabet::Protein);
+        assert_eq!(
+            detect_alphabet(b"ACGTACGTACGTACGTACGTAcgtE"),
+            Alphabet::Nucleotide
+        );
+        assert_eq!(detect_alphabet(b""), Alphabet::Auto);
+    }
+
+    #[test]
+    fn test_aa_count() {
+        let mut count = AaCount::default();
+        count.add_sequence(b"MKV*Lxb-*");
+        assert_eq!(count.get(b'M'), 1);
+        assert_eq!(count.get(b'k'), 1);
+        assert_eq!(count.get(b'X'), 1);
+        assert_eq!(count.num_internal_stops, 1);
+        assert_eq!(count.num_other, 1);
+        assert_eq!(count.num_non_standard(), 1);
+    }
+
+    #[test]
+    fn test_protein_builder() {
+        let mut builder = ProteinBuilder::default();
+        builder.add_sequence("p1", b"MKVLA*");
+        builder.add_sequence("p2", b"KV*LAMKV");
+        builder.add_sequence("p1", b"MA");
+        let stats = builder.finish(&[50]);
+        assert_eq!(stats.num_sequences, 3);
+        assert_eq!(stats.total_length, 15);
+        assert_eq!(stats.lengths.largest, 8);
+        assert_eq!(stats.lengths.smallest, 2);
+        assert_eq!(
+            stats.nx,
+            vec![NxStat {
+                percent: 50,
+                n: 8,
+                l: 1
+            }]
+        );
+        assert_eq!(stats.num_internal_stops, 1);
+        assert_eq!(stats.num_sequences_internal_stops, 1);
+        assert_eq!(stats.num_no_start_m, 1);
+        assert_eq!(stats.aa_count.get(b'K'), 3);
+        assert_eq!(stats.ids.num_duplicate, 1);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::calc::reverse_complement;
+
+/// An end counts as telomeric with at least this many tandem copies of the motif.
+pub const MIN_TELOMERE_COPIES: usize = 10;
+
+/// Telomere repeats at both ends of the contigs of one assembly.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TelomereStats {
+    pub motif: String,
+    pub window: usize,
+    pub num_contigs_one_end: usize,
+    pub num_contigs_both_ends: usize,
+}
+
+impl TelomereStats {
+    pub fn new(motif: &str, window: usize) -> Self {
+        TelomereStats {
+            motif: motif.to_string(),
+            window,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the repeat lengths at the start and end of one contig.
+    pub fn add(&mut self, (start, end): (usize, usize)) {
+        let min_length = MIN_TELOMERE_COPIES * self.motif.len();
+        match (start >= min_length, end >= min_length) {
+            (true, true) => self.num_contigs_both_ends += 1,
+            (true, false) | (false, true) => self.num_contigs_one_end += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Arrays count towards a contig end if they start within this many bases of it,
+/// or of the array before them, so interior repeats are not taken as telomeres.
+pub const MAX_TELOMERE_OFFSET: usize = 100;
+
+/// Tandem arrays (>= 2 copies) of `motif` or its reverse complement as (start, end), case-insensitive.
+fn tandem_arrays(seq: &[u8], motif: &[u8]) -> Vec<(usize, usize)> {
+    let m = motif.len();
+    if m == 0 {
+        return Vec::new();
+    }
+    let motif = motif.to_ascii_uppercase();
+    let motifs = [motif.clone(), reverse_complement(&motif)];
+    let matches =
+        |i: usize, motif: &[u8]| i + m <= seq.len() && seq[i..i + m].eq_ignore_ascii_case(motif);
+    let mut arrays = Vec::new();
+    let mut i: usize = 0;
+    while i < seq.len() {
+        let copies = motifs
+            .iter()
+            .map(|motif| {
+                let mut copies = 0;
+                while matches(i + copies * m, motif) {
+                    copies += 1;
+                }
+                copies
+            })
+            .max()
+            .unwrap_or(0);
+        if copies >= 2 {
+            arrays.push((i, i + copies * m));
+            i += copies * m;
+        } else {
+            i += 1;
+        }
+    }
+    arrays
+}
+
+/// Bases covered by the arrays chained to position `end` of the sequence,
+/// each within `MAX_TELOMERE_OFFSET` of the end or of the previous array.
+fn anchored_length(arrays: impl Iterator<Item = (usize, usize)>, end: usize) -> usize {
+    let mut covered = 0;
+    let mut last = end;
+    for (near, far) in arrays {
+        if near.abs_diff(last) > MAX_TELOMERE_OFFSET {
+            break;
+        }
+        covered += near.abs_diff(far);
+        last = far;
+    }
+    covered
+}
+
+/// Telomere repeat lengths at the start and end of a contig. Only arrays within the first
+/// and last `window` bases (at most half the contig each) that reach the contig end are counted.
+pub fn telomere_ends(seq: &[u8], motif: &[u8], window: usize) -> (usize, usize) {
+    let window = window.min(seq.len() / 2);
+    let start_arrays = tandem_arrays(&seq[..window], motif);
+    let end_arrays = tandem_arrays(&seq[seq.len() - window..], motif);
+    (
+        anchored_length(start_arrays.into_iter(), 0),
+        anchored_length(
+            end_arrays
+                .into_iter()
+                .rev()
+                .map(|(start, end)| (end, start)),
+            window,
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::telomere::{tandem_arrays, telomere_ends, TelomereStats};
+
+    #[test]
+    fn test_tandem_arrays() {
+        // single copies are not counted
+        assert_eq!(tandem_arrays(b"ACTTAGGGAC", b"TTAGGG"), []);
+        assert_eq!(tandem_arrays(b"ttagggTTAGGGAC", b"TTAGGG"), [(0, 12)]);
+        assert_eq!(
+            tandem_arrays(b"CCCTAACCCTAACCCTAAGGTTAGGGTTAGGG", b"TTAGGG"),
+            [(0, 18), (20, 32)]
+        );
+        assert_eq!(tandem_arrays(b"", b"TTAGGG"), []);
+    }
+
+    #[test]
+    fn test_telomere_ends() {
+        let seq = [
+            b"CCCTAA".repeat(10),
+            b"ACGT".repeat(10),
+            b"TTAGGG".repeat(3),
+        ]
+        .concat();
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 50), (48, 18));
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 12), (12, 12));
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 10), (0, 0));
+        // windows are clamped to half the contig, so no array is counted at both ends
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 1000), (54, 18));
+        // interrupted arrays within one window are chained across the gap
+        let interrupted = [b"TTAGGG".repeat(5), b"AC".to_vec(), b"TTAGGG".repeat(5)].concat();
+        let seq = [interrupted.clone(), b"ACGT".repeat(20), interrupted].concat();
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 1000), (60, 60));
+        let seq = [
+            b"CCCTAA".repeat(5),
+            b"ACGT".repeat(30),
+            b"CCCTAA".repeat(5),
+            b"ACGT".repeat(100),
+        ]
+        .concat();
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 1000), (30, 0));
+    }
+
+    #[test]
+    fn test_telomere_interior() {
+        let seq = [b"ACGT".repeat(50), b"TTAGGG".repeat(12), b"ACGT".repeat(50)].concat();
+        assert_eq!(seq.len(), 472);
+        assert_eq!(telomere_ends(&seq, b"TTAGGG", 5000), (0, 0));
+    }
+
+    #[test]
+    fn test_telomere_stats() {
+        let mut stats = TelomereStats::new("TTAGGG", 100);
+        stats.add((60, 60));
+        stats.add((0, 60));
+        stats.add((59, 0));
+        assert_eq!(stats.num_contigs_both_ends, 1);
+        assert_eq!(stats.num_contigs_one_end, 1);
+    }
+}
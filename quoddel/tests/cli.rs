@@ -8,6 +8,16 @@ type TestResult = Result<(), Box<dyn std::error::Error>>;
 const PRG: &str = "quoddel";
 const FA1: &str = "tests/inputs/fasta1.fa";
 const FA2: &str = "tests/inputs/fasta2.fa";
+const SCAFFOLDS: &str = "tests/inputs/scaffolds.fa";
+const IUPAC: &str = "tests/inputs/iupac.fa";
+const SOFTMASKED: &str = "tests/inputs/softmasked.fa";
+const TELOMERES: &str = "tests/inputs/telomeres.fa";
+const PROTEINS: &str = "tests/inputs/proteins.fa";
+const LOW_COMPLEXITY: &str = "tests/inputs/lowcomplexity.fa";
+const DUPLICATES: &str = "tests/inputs/duplicates.fa";
+const IDS: &str = "tests/inputs/ids.fa";
+const CIRCULAR: &str = "tests/inputs/circular.fa";
+const ORFS: &str = "tests/inputs/orfs.fa";
 
 // --------------------------------------------------
 fn gen_bad_file() -> String {
@@ -24,10 +34,19 @@ fn gen_bad_file() -> String {
     }
 }
 
+// --------------------------------------------------
+fn gen_out_file() -> String {
+    let name = gen_bad_file();
+    std::env::temp_dir()
+        .join(format!("quoddel_{}.tsv", name))
+        .to_string_lossy()
+        .to_string()
+}
+
 #[test]
 fn dies_bad_file() -> TestResult {
     let bad = gen_bad_file();
-    let expected = format!(".* [(]os error 2[)]");
+    let expected = ".* [(]os error 2[)]".to_string();
     Command::cargo_bin(PRG)?
         .arg(bad)
         .assert()
@@ -48,11 +67,17 @@ fn fasta1_gc() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 10")
-                .and(predicate::str::contains("num_contigs_ge0: 4"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 4,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 2"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 84"))
-                .and(predicate::str::contains("total_length_ge0: 90")) //todo greater equal..
-                .and(predicate::str::contains("gc_percent_ge_cutoff: 0.2857143")),
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 90,",
+                )?) //todo greater equal..
+                .and(predicate::str::contains(
+                    "gc_percent_ge_cutoff: 0.2857142857142857,",
+                )),
         );
     Ok(())
 }
@@ -68,11 +93,17 @@ fn fasta1_gc_m0() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 0")
-                .and(predicate::str::contains("num_contigs_ge0: 4"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 4,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 4"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 90"))
-                .and(predicate::str::contains("total_length_ge0: 90")) //todo greater equal..
-                .and(predicate::str::contains("gc_percent_ge_cutoff: 0.26666668")),
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 90,",
+                )?) //todo greater equal..
+                .and(predicate::str::contains(
+                    "gc_percent_ge_cutoff: 0.26666666666666666,",
+                )),
         );
     Ok(())
 }
@@ -89,11 +120,17 @@ fn fasta1_gc_m1() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 1")
-                .and(predicate::str::contains("num_contigs_ge0: 4"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 4,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 3"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 90"))
-                .and(predicate::str::contains("total_length_ge0: 90")) //todo greater equal..
-                .and(predicate::str::contains("gc_percent_ge_cutoff: 0.26666668")),
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 90,",
+                )?) //todo greater equal..
+                .and(predicate::str::contains(
+                    "gc_percent_ge_cutoff: 0.26666666666666666,",
+                )),
         );
     Ok(())
 }
@@ -110,15 +147,17 @@ fn fasta2_gc_m1_n90_l90_n50_l50() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 1")
-                .and(predicate::str::contains("num_contigs_ge0: 7"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 7,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 7"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 525"))
-                .and(predicate::str::contains("total_length_ge0: 525")) //todo greater equal..
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 525,",
+                )?) //todo greater equal..
                 .and(predicate::str::contains("gc_percent_ge_cutoff: 0.422"))
-                .and(predicate::str::contains("n50_ge_cutoff: 70"))
-                .and(predicate::str::contains("l50: 2"))
-                .and(predicate::str::contains("n90_ge_cutoff: 35"))
-                .and(predicate::str::contains("l90: 6")),
+                .and(predicate::str::is_match(r"percent: 50,\s+n: 70,\s+l: 2,")?)
+                .and(predicate::str::is_match(r"percent: 90,\s+n: 35,\s+l: 6,")?),
         );
     Ok(())
 }
@@ -137,15 +176,17 @@ pub fn fasta2_gc_m1_n90_l90_n50_l50_stdin() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 1")
-                .and(predicate::str::contains("num_contigs_ge0: 7"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 7,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 7"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 525"))
-                .and(predicate::str::contains("total_length_ge0: 525"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 525,",
+                )?)
                 .and(predicate::str::contains("gc_percent_ge_cutoff: 0.422"))
-                .and(predicate::str::contains("n50_ge_cutoff: 70"))
-                .and(predicate::str::contains("l50: 2"))
-                .and(predicate::str::contains("n90_ge_cutoff: 35"))
-                .and(predicate::str::contains("l90: 6")),
+                .and(predicate::str::is_match(r"percent: 50,\s+n: 70,\s+l: 2,")?)
+                .and(predicate::str::is_match(r"percent: 90,\s+n: 35,\s+l: 6,")?),
         );
     Ok(())
 }
@@ -161,11 +202,17 @@ fn fasta1_gc_m0_stdin() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 0")
-                .and(predicate::str::contains("num_contigs_ge0: 4"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 4,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 4"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 90"))
-                .and(predicate::str::contains("total_length_ge0: 90"))
-                .and(predicate::str::contains("gc_percent_ge_cutoff: 0.26666668")),
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 90,",
+                )?)
+                .and(predicate::str::contains(
+                    "gc_percent_ge_cutoff: 0.26666666666666666,",
+                )),
         );
     Ok(())
 }
@@ -182,12 +229,1114 @@ fn fasta1_gc_m2_stdin() -> TestResult {
         .success()
         .stdout(
             predicate::str::contains("min_contig_length: 1")
-                .and(predicate::str::contains("num_contigs_ge0: 4"))
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: 4,",
+                )?)
                 .and(predicate::str::contains("num_contigs_ge_cutoff: 3"))
                 .and(predicate::str::contains("total_length_ge_cutoff: 90"))
-                .and(predicate::str::contains("total_length_ge0: 90"))
-                .and(predicate::str::contains("gc_percent_ge_cutoff: 0.26666668")),
+                .and(predicate::str::is_match(
+                    r"min_length: 0,\s+num_contigs: \d+,\s+total_length: 90,",
+                )?)
+                .and(predicate::str::contains(
+                    "gc_percent_ge_cutoff: 0.26666666666666666,",
+                )),
         );
     Ok(())
 }
 //todo test n per 100k bp
+
+#[test]
+fn fasta2_ng50_est_ref_size() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-m")
+        .arg("1")
+        .arg("-g")
+        .arg("1k")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("estimated reference length\t1000\n")
+                .and(predicate::str::contains("NG50\t35\n"))
+                .and(predicate::str::contains("LG50\t7\n"))
+                .and(predicate::str::contains("NG90\t-\n"))
+                .and(predicate::str::contains("LG90\t-\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn fasta2_ng50_est_ref_fasta() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-m")
+        .arg("1")
+        .arg("--est-ref-size")
+        .arg(FA2)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("estimated reference length\t525\n")
+                .and(predicate::str::contains("NG50\t70\n"))
+                .and(predicate::str::contains("LG90\t6\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn fasta2_aun_aung() -> TestResult {
+    // 210^2 + 3 * 70^2 + 3 * 35^2 = 62475
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-m")
+        .arg("1")
+        .arg("-g")
+        .arg("1k")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("auN\t119.0\n").and(predicate::str::contains("auNG\t62.5\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn fasta2_nx_percentages() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-m")
+        .arg("1")
+        .arg("--nx")
+        .arg("95,10,50")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("N10\t210\nN50\t70\nN95\t35\n")
+                .and(predicate::str::contains("L10\t1\nL50\t2\nL95\t7\n"))
+                .and(predicate::str::contains("N90").not()),
+        );
+    Ok(())
+}
+
+#[test]
+fn dies_bad_nx_percentage() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("--nx")
+        .arg("50,101")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid Nx percentage '101'"));
+    Ok(())
+}
+
+#[test]
+fn dies_genome_size_overflow() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-g")
+        .arg("20000000000G")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid genome size '20000000000G'",
+        ));
+    Ok(())
+}
+
+#[test]
+fn dies_genome_size_zero() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-g")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid genome size '0': must be greater than 0",
+        ));
+    let empty = gen_out_file();
+    fs::write(&empty, "")?;
+    let result = Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-g")
+        .arg(&empty)
+        .assert();
+    fs::remove_file(&empty)?;
+    result
+        .failure()
+        .stderr(predicate::str::contains("must be greater than 0"));
+    Ok(())
+}
+
+#[test]
+fn fasta2_nx_curve() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg(FA1)
+        .arg("-m")
+        .arg("1")
+        .arg("-g")
+        .arg("600")
+        .arg("--nx-curve")
+        .arg(&out)
+        .assert()
+        .success();
+    let curve = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    let lines: Vec<&str> = curve.lines().collect();
+    assert_eq!(lines.len(), 1 + 2 * 101);
+    assert_eq!(lines[0], "assembly\tx\tNx\tLx\tNGx\tLGx");
+    assert_eq!(lines[1], format!("{}\t0\t210\t1\t210\t1", FA2));
+    assert_eq!(lines[51], format!("{}\t50\t70\t2\t70\t3", FA2));
+    assert_eq!(lines[89], format!("{}\t88\t35\t6\t-\t-", FA2));
+    assert_eq!(lines[102], format!("{}\t0\t70\t1\t70\t1", FA1));
+    Ok(())
+}
+
+#[test]
+fn fasta2_length_thresholds() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA2)
+        .arg("-m")
+        .arg("50")
+        .arg("--thresholds")
+        .arg("0,40,0.1k,1M")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(
+                "num contigs (>= 0 bp)\t7\n\
+                 num contigs (>= 40 bp)\t4\n\
+                 num contigs (>= 100 bp)\t1\n\
+                 num contigs (>= 1000000 bp)\t0\n\
+                 total length (>= 0 bp)\t525\n\
+                 total length (>= 40 bp)\t420\n\
+                 total length (>= 100 bp)\t210\n\
+                 total length (>= 1000000 bp)\t0\n",
+            )
+            .and(predicate::str::contains("(>= 5000 bp)").not()),
+        );
+    Ok(())
+}
+
+#[test]
+fn scaffolds_broken_contigs() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("--scaffolds")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(format!("Assembly\t{0}\t{0}_broken\n", SCAFFOLDS))
+                .and(predicate::str::contains(
+                    "num contigs\t3\t4\nlargest contig\t40\t30\n",
+                ))
+                .and(predicate::str::contains("num scaffolds with gaps\t1\t-\n"))
+                .and(predicate::str::contains("total length\t83\t73\n"))
+                .and(predicate::str::contains("N50\t30\t20\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn scaffolds_min_gap() -> TestResult {
+    // the 5 N gap in scaf2 splits as well
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("-s")
+        .arg("--min-gap")
+        .arg("5")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num contigs\t3\t5\n")
+                .and(predicate::str::contains("num scaffolds with gaps\t2\t-\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn scaffolds_side_tables_without_broken_contigs() -> TestResult {
+    let id_issues = gen_out_file();
+    let per_contig = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("--scaffolds")
+        .arg("--circular")
+        .arg("--id-issues")
+        .arg(&id_issues)
+        .arg("--per-contig")
+        .arg(&per_contig)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num circular contigs\t0\t-\n").and(predicate::str::contains(
+                "num duplicate sequence IDs\t0\t-\n",
+            )),
+        );
+    let id_issues_table = fs::read_to_string(&id_issues)?;
+    let per_contig_table = fs::read_to_string(&per_contig)?;
+    fs::remove_file(&id_issues)?;
+    fs::remove_file(&per_contig)?;
+    assert_eq!(id_issues_table, "assembly\tid\tissue\n");
+    assert_eq!(per_contig_table.lines().count(), 4);
+    assert!(!per_contig_table.contains("_broken"));
+    Ok(())
+}
+
+#[test]
+fn scaffolds_not_split_by_default() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num contigs\t3\n")
+                .and(predicate::str::contains("_broken").not())
+                .and(predicate::str::contains("num scaffolds with gaps").not()),
+        );
+    Ok(())
+}
+
+#[test]
+fn scaffolds_gap_stats() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num gaps\t2\n")
+                .and(predicate::str::contains("total gap length\t15\n"))
+                .and(predicate::str::contains("largest gap\t10\n"))
+                .and(predicate::str::contains("mean gap length\t7.5\n"))
+                .and(predicate::str::contains("median gap length\t7.5\n"))
+                .and(predicate::str::contains("num gaps of 100 Ns\t0\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn iupac_ambiguous_and_other() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(IUPAC)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("GC (%)\t50.000\n")
+                .and(predicate::str::contains(
+                    "num ambiguous bases per 100 kbp\t50000.000\n",
+                ))
+                .and(predicate::str::contains("num other characters\t2\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn iupac_gc_options() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(IUPAC)
+        .arg("-m")
+        .arg("1")
+        .arg("--gc-count-s")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GC (%)\t58.333\n"));
+    Command::cargo_bin(PRG)?
+        .arg(IUPAC)
+        .arg("-m")
+        .arg("1")
+        .arg("--gc-ambiguous")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GC (%)\t20.000\n"));
+    Ok(())
+}
+
+#[test]
+fn softmasked_bases() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SOFTMASKED)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("soft-masked bases\t20\n")
+                .and(predicate::str::contains("soft-masked (%)\t52.632\n"))
+                .and(predicate::str::contains("num soft-masked intervals\t3\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn fasta1_gc_distribution() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(FA1)
+        .arg("-m")
+        .arg("1")
+        .arg("--gc-histogram")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GC (%)\t26.667\nN50\t70\n").and(
+            predicate::str::contains(
+                "mean contig GC (%)\t19.048\ncontig GC (%) std dev\t13.469\n\
+                 min contig GC (%)\t0.000\nmax contig GC (%)\t28.571\n",
+            ),
+        ));
+    let histogram = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    let lines: Vec<&str> = histogram.lines().collect();
+    assert_eq!(lines.len(), 1 + 100);
+    assert_eq!(lines[0], "assembly\tGC (%)\tnum contigs\ttotal length");
+    assert_eq!(lines[1], format!("{}\t0\t1\t6", FA1));
+    assert_eq!(lines[29], format!("{}\t28\t2\t84", FA1));
+    assert_eq!(lines[30], format!("{}\t29\t0\t0", FA1));
+    Ok(())
+}
+
+#[test]
+fn scaffolds_dinucleotides_cpg() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("--cpg")
+        .arg("-m")
+        .arg("1")
+        .arg("--dinucleotides")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "max contig GC (%)\t100.000\nCpG o/e\t1.569\n",
+        ));
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 1 + 3);
+    assert_eq!(
+        lines[0],
+        "assembly\tcontig\tlength\tAA\tAC\tAG\tAT\tCA\tCC\tCG\tCT\tGA\tGC\tGG\tGT\tTA\tTC\tTG\tTT\tCpG o/e"
+    );
+    assert_eq!(
+        lines[1],
+        format!(
+            "{}\tscaf1\t40\t0\t8\t0\t0\t0\t0\t7\t0\t0\t0\t0\t7\t6\t0\t0\t0\t3.750",
+            SCAFFOLDS
+        )
+    );
+    assert!(lines[3].ends_with("\tNaN"));
+    Ok(())
+}
+
+#[test]
+fn scaffolds_kmer_spectrum() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("-k")
+        .arg("3")
+        .arg("--kmer-histogram")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num 3-mers\t58\n")
+                .and(predicate::str::contains("num distinct 3-mers\t3\n"))
+                .and(predicate::str::contains(
+                    "distinct 3-mers occurring more than once (%)\t100.000\n",
+                )),
+        );
+    let histogram = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        histogram,
+        format!(
+            "assembly\tk\tmultiplicity\tnum distinct k-mers\n\
+             {0}\t3\t12\t1\n{0}\t3\t18\t1\n{0}\t3\t28\t1\n",
+            SCAFFOLDS
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn dies_bad_kmer_size() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-k")
+        .arg("32")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid k-mer size '32'"));
+    Ok(())
+}
+
+#[test]
+fn scaffolds_kmer_spectrum_capped() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("-k")
+        .arg("3")
+        .arg("--kmer-max-distinct")
+        .arg("2")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num distinct 3-mers\t2\n")
+                .and(predicate::str::contains("3-mer counting capped\tyes\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn scaffolds_kmers_counted_once() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("-k")
+        .arg("3")
+        .arg("--scaffolds")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num 3-mers\t58\t-\n")
+                .and(predicate::str::contains("3-mer counting capped\tno\t-\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn scaffolds_homopolymers() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num G homopolymers (>= 5 bp)\t1\n")
+                .and(predicate::str::contains(
+                    "num A homopolymers (>= 5 bp)\t0\n",
+                ))
+                .and(predicate::str::contains("longest G homopolymer\t30\n"))
+                .and(predicate::str::contains(
+                    "bases in homopolymers (>= 5 bp) (%)\t36.145\n",
+                )),
+        );
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .arg("--min-homopolymer")
+        .arg("31")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num G homopolymers (>= 31 bp)\t0\n")
+                .and(predicate::str::contains("longest G homopolymer\t30\n")),
+        );
+    Ok(())
+}
+
+#[test]
+fn telomeres_at_contig_ends() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(TELOMERES)
+        .arg("-m")
+        .arg("1")
+        .arg("--telomeres")
+        .arg("--telomere-window")
+        .arg("100")
+        .arg("--telomere-table")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num contigs with telomere at one end\t1\n").and(
+                predicate::str::contains("num contigs with telomere at both ends\t1\n"),
+            ),
+        );
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tcontig\tlength\tstart telomere length\tend telomere length\n\
+             {0}\tchr1\t264\t72\t72\n{0}\tchr2\t210\t0\t90\n{0}\tchr3\t160\t0\t0\n",
+            TELOMERES
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn telomeres_other_motif() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(TELOMERES)
+        .arg("-m")
+        .arg("1")
+        .arg("--telomeres")
+        .arg("--telomere-motif")
+        .arg("TTTAGGG")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num contigs with telomere at one end\t0\n").and(
+                predicate::str::contains("num contigs with telomere at both ends\t0\n"),
+            ),
+        );
+    Ok(())
+}
+
+#[test]
+fn telomeres_not_scanned_by_default() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(TELOMERES)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("num contigs with telomere").not());
+    Ok(())
+}
+
+#[test]
+fn length_histogram_log10_bins() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(FA1)
+        .arg(FA2)
+        .arg("-m")
+        .arg("0")
+        .arg("--length-histogram")
+        .arg(&out)
+        .assert()
+        .success();
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tmin length\tmax length\tnum contigs\ttotal length\n\
+             {0}\t0\t9\t2\t6\n{0}\t10\t99\t2\t84\n\
+             {1}\t0\t9\t0\t0\n{1}\t10\t99\t6\t315\n{1}\t100\t999\t1\t210\n",
+            FA1, FA2
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn length_histogram_user_bins() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(FA1)
+        .arg("-m")
+        .arg("0")
+        .arg("--length-histogram")
+        .arg(&out)
+        .arg("--length-bins")
+        .arg("20,50")
+        .assert()
+        .success();
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tmin length\tmax length\tnum contigs\ttotal length\n\
+             {0}\t0\t19\t3\t20\n{0}\t20\t49\t0\t0\n{0}\t50\t-\t1\t70\n",
+            FA1
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn contig_length_summary() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA1)
+        .arg("-m")
+        .arg("10")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("largest contig\t70\ntotal length\t84\n").and(
+                predicate::str::contains(
+                    "num N's per 100 kbp\t0.000\n\
+                     smallest contig\t14\nmean contig length\t42.0\n\
+                     median contig length\t42.0\ncontig length std dev\t28.0\n\
+                     largest contig (>= 0 bp)\t70\nsmallest contig (>= 0 bp)\t0\n\
+                     mean contig length (>= 0 bp)\t22.5\nmedian contig length (>= 0 bp)\t10.0\n\
+                     contig length std dev (>= 0 bp)\t27.9\n",
+                ),
+            ),
+        );
+    Ok(())
+}
+
+#[test]
+fn proteins_auto_detected() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(PROTEINS)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num sequences\t3\ntotal length\t28\n")
+                .and(predicate::str::contains("N50\t14\n"))
+                .and(predicate::str::contains("L50\t1\n"))
+                .and(predicate::str::contains("A (%)\t17.857\n"))
+                .and(predicate::str::contains("X (%)\t7.143\n"))
+                .and(predicate::str::contains("num internal stops\t1\n"))
+                .and(predicate::str::contains(
+                    "num sequences not starting with M\t1\n",
+                ))
+                .and(predicate::str::contains("GC (%)").not()),
+        );
+    Ok(())
+}
+
+#[test]
+fn proteins_forced_nucleotide() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(PROTEINS)
+        .arg("--alphabet")
+        .arg("nucleotide")
+        .arg("-m")
+        .arg("0")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("total length\t29\n")
+                .and(predicate::str::contains("num internal stops").not()),
+        );
+    Ok(())
+}
+
+#[test]
+fn nucleotides_forced_protein() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(FA1)
+        .arg("--alphabet")
+        .arg("protein")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("num sequences\t4\ntotal length\t90\n").and(
+                predicate::str::contains("num sequences not starting with M\t4\n"),
+            ),
+        );
+    Ok(())
+}
+
+#[test]
+fn low_complexity_bed() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(LOW_COMPLEXITY)
+        .arg("--low-complexity")
+        .arg("-m")
+        .arg("1")
+        .arg("--low-complexity-bed")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("low-complexity bases\t80\n")
+                .and(predicate::str::contains("low-complexity (%)\t13.793\n")),
+        );
+    let bed = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(bed, "lc1\t150\t230\n");
+    Ok(())
+}
+
+#[test]
+fn low_complexity_bed_per_assembly() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(FA1)
+        .arg(LOW_COMPLEXITY)
+        .arg("--low-complexity")
+        .arg("-m")
+        .arg("1")
+        .arg("--low-complexity-bed")
+        .arg(&out)
+        .assert()
+        .success();
+    assert!(fs::metadata(&out).is_err());
+    let first = out.replace(".tsv", ".1.tsv");
+    let second = out.replace(".tsv", ".2.tsv");
+    let beds = (fs::read_to_string(&first), fs::read_to_string(&second));
+    fs::remove_file(&first)?;
+    fs::remove_file(&second)?;
+    assert_eq!(beds.0?, "");
+    assert_eq!(beds.1?, "lc1\t150\t230\n");
+    Ok(())
+}
+
+#[test]
+fn low_complexity_threshold() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(LOW_COMPLEXITY)
+        .arg("--low-complexity")
+        .arg("-m")
+        .arg("1")
+        .arg("--dust-threshold")
+        .arg("100")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("low-complexity bases\t0\n"));
+    Ok(())
+}
+
+#[test]
+fn dies_bad_dust_window() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(LOW_COMPLEXITY)
+        .arg("--dust-window")
+        .arg("3")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid DUST window '3'"));
+    Ok(())
+}
+
+#[test]
+fn duplicate_contigs() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(DUPLICATES)
+        .arg("--find-duplicates")
+        .arg("-m")
+        .arg("1")
+        .arg("--duplicates")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "num duplicate groups\t2\nnum duplicate contigs\t3\nduplicated length\t41\n",
+        ));
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tgroup\tcontig\tlength\torientation\n\
+             {0}\t1\tctg1\t16\t+\n{0}\t1\tctg3\t16\t-\n{0}\t1\tctg4\t16\t+\n\
+             {0}\t2\tctg2\t9\t+\n{0}\t2\tctg5\t9\t-\n",
+            DUPLICATES
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn duplicate_contigs_below_cutoff() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(DUPLICATES)
+        .arg("--find-duplicates")
+        .arg("-m")
+        .arg("10")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "num duplicate groups\t1\nnum duplicate contigs\t2\nduplicated length\t32\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn sequence_id_issues() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(IDS)
+        .arg("--id-issues")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "num duplicate sequence IDs\t1\nnum empty sequence IDs\t1\n\
+             num sequence IDs with problematic characters\t3\n",
+        ));
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tid\tissue\n{0}\tctg1\tduplicate\n{0}\t\tempty\n\
+             {0}\tgi|123|ref\tproblematic characters\n\
+             {0}\tchr1:1-100\tproblematic characters\n\
+             {0}\tcontig_ä\tproblematic characters\n",
+            IDS
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn sequence_ids_ok() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "num duplicate sequence IDs\t0\nnum empty sequence IDs\t0\n\
+             num sequence IDs with problematic characters\t0\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn circular_contigs() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(CIRCULAR)
+        .arg("--circular")
+        .arg("-m")
+        .arg("1")
+        .arg("--circular-table")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "num circular contigs\t3\ntotal length of circular contigs\t283\n",
+        ));
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tcontig\tlength\tcircular\theader tag\tend overlap\n\
+             {0}\t1\t100\tyes\tyes\tno\n{0}\t2\t80\tno\tno\tno\n\
+             {0}\tplasmid\t102\tyes\tno\tyes\n\
+             {0}\tNODE_4_length_50_cov_3.0_type_circular\t81\tyes\tyes\tyes\n",
+            CIRCULAR
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn circular_contigs_longer_overlap() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(CIRCULAR)
+        .arg("--circular")
+        .arg("-m")
+        .arg("1")
+        .arg("--circular-overlap")
+        .arg("41")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "num circular contigs\t2\ntotal length of circular contigs\t181\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn per_contig_sorted_by_length() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(IUPAC)
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("14")
+        .arg("--entropy")
+        .arg("--per-contig")
+        .arg(&out)
+        .assert()
+        .success();
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    assert_eq!(
+        table,
+        format!(
+            "assembly\tcontig\tlength\tlength >= cutoff\tGC (%)\tnum N's\t\
+             num ambiguous bases\tsoft-masked bases\tentropy\tlow entropy\n\
+             {0}\tamb1\t16\tyes\t50.000\t2\t10\t0\t2.000\tno\n\
+             {0}\tamb2\t8\tno\t50.000\t0\t2\t0\t2.000\tno\n\
+             {1}\tscaf1\t40\tyes\t50.000\t10\t0\t0\t1.997\tno\n\
+             {1}\tscaf3\t30\tyes\t100.000\t0\t0\t0\t0.000\tyes\n\
+             {1}\tscaf2\t13\tno\t50.000\t5\t0\t0\t2.000\tno\n",
+            IUPAC, SCAFFOLDS
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn per_contig_soft_masked() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(SOFTMASKED)
+        .arg("-m")
+        .arg("0")
+        .arg("--per-contig")
+        .arg(&out)
+        .assert()
+        .success();
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    let soft_masked: usize = table
+        .lines()
+        .skip(1)
+        .map(|line| line.split('\t').nth(7).unwrap().parse::<usize>().unwrap())
+        .sum();
+    let report = String::from_utf8(
+        Command::cargo_bin(PRG)?
+            .arg(SOFTMASKED)
+            .arg("-m")
+            .arg("0")
+            .output()?
+            .stdout,
+    )?;
+    assert!(report.contains(&format!("soft-masked bases\t{}\n", soft_masked)));
+    Ok(())
+}
+
+#[test]
+fn contig_entropy() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("--entropy")
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "mean contig entropy\t1.332\nnum contigs with entropy < 1.5\t1\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn contig_kmer_entropy() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("--entropy")
+        .arg("-m")
+        .arg("1")
+        .arg("--entropy-k")
+        .arg("2")
+        .arg("--min-entropy")
+        .arg("2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "mean contig 2-mer entropy\t1.193\nnum contigs with 2-mer entropy < 2\t3\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn dies_bad_entropy_k() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("--entropy-k")
+        .arg("9")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid entropy k-mer size '9', must be 1 to 8",
+        ));
+    Ok(())
+}
+
+#[test]
+fn longest_orfs() -> TestResult {
+    let out = gen_out_file();
+    Command::cargo_bin(PRG)?
+        .arg(ORFS)
+        .arg("-m")
+        .arg("1")
+        .arg("--orfs")
+        .arg("--per-contig")
+        .arg(&out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "largest ORF (aa)\t121\nmean longest ORF (aa)\t81.0\n\
+             median longest ORF (aa)\t81.0\nlongest ORF std dev\t40.0\n\
+             contigs with ORF >= 100 aa (%)\t50.000\n",
+        ));
+    let table = fs::read_to_string(&out)?;
+    fs::remove_file(&out)?;
+    let header = table.lines().next().unwrap();
+    assert!(header.ends_with("\tsoft-masked bases\tlongest ORF (aa)"));
+    let longest_orfs: Vec<&str> = table
+        .lines()
+        .skip(1)
+        .map(|line| line.rsplit('\t').next().unwrap())
+        .collect();
+    assert_eq!(longest_orfs, vec!["121", "41"]);
+    Ok(())
+}
+
+#[test]
+fn longest_orfs_min_length() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(ORFS)
+        .arg("-m")
+        .arg("1")
+        .arg("--orfs")
+        .arg("--min-orf")
+        .arg("30")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "contigs with ORF >= 30 aa (%)\t100.000\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn longest_orfs_not_searched_by_default() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(ORFS)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ORF").not());
+    Ok(())
+}
+
+#[test]
+fn optional_analyses_off_by_default() -> TestResult {
+    Command::cargo_bin(PRG)?
+        .arg(SCAFFOLDS)
+        .arg("-m")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("CpG o/e")
+                .or(predicate::str::contains("low-complexity"))
+                .or(predicate::str::contains("duplicate groups"))
+                .or(predicate::str::contains("circular"))
+                .or(predicate::str::contains("entropy"))
+                .not(),
+        );
+    Ok(())
+}